@@ -3,23 +3,7 @@ use lieutenant::{command, CommandDispatcher, Context};
 use thiserror::Error;
 
 #[derive(Debug, Error, PartialEq)]
-enum Error {
-    #[error("failed to parse int")]
-    ParsingInt,
-}
-
-impl From<std::num::ParseIntError> for Error
-{
-    fn from(_: std::num::ParseIntError) -> Self {
-        Error::ParsingInt
-    }
-}
-
-impl From<std::convert::Infallible> for Error {
-    fn from(_: std::convert::Infallible) -> Self {
-        panic!()
-    }
-}
+enum Error {}
 
 fn single_command(c: &mut Criterion) {
     struct State;
@@ -37,13 +21,11 @@ fn single_command(c: &mut Criterion) {
     dispatcher.register(command).unwrap();
 
     let mut nodes = Vec::new();
-    let mut errors = Vec::new();
 
     c.bench_function("dispatch single command", |b| {
         b.iter(|| {
             assert!(smol::block_on(dispatcher.dispatch(
                 &mut nodes,
-                &mut errors,
                 &mut State,
                 black_box("command")
             ))
@@ -144,48 +126,41 @@ fn multiple_commands(c: &mut Criterion) {
         .with(command_5);
 
     let mut nodes = Vec::new();
-    let mut errors = Vec::new();
 
     c.bench_function("dispatch multiple commands", |b| {
         b.iter(|| {
             assert!(smol::block_on(dispatcher.dispatch(
                 &mut nodes,
-                &mut errors,
                 &mut State,
                 "command"
             ))
             .is_ok());
             assert!(smol::block_on(dispatcher.dispatch(
                 &mut nodes,
-                &mut errors,
                 &mut State,
                 "command 4"
             ))
             .is_ok());
             assert!(smol::block_on(dispatcher.dispatch(
                 &mut nodes,
-                &mut errors,
                 &mut State,
                 "command 4 hello"
             ))
             .is_ok());
             assert!(smol::block_on(dispatcher.dispatch(
                 &mut nodes,
-                &mut errors,
                 &mut State,
                 "command hello hello"
             ))
             .is_ok());
             assert!(smol::block_on(dispatcher.dispatch(
                 &mut nodes,
-                &mut errors,
                 &mut State,
                 "command 4 4 4"
             ))
             .is_ok());
             assert!(smol::block_on(dispatcher.dispatch(
                 &mut nodes,
-                &mut errors,
                 &mut State,
                 "command a a a"
             ))
@@ -1,18 +1,86 @@
 use darling::ast::GenericParamExt;
 use darling::FromMeta;
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error::*;
 use quote::quote;
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, AttributeArgs, Block, FnArg, ItemFn, Pat, PatType, Type, ReturnType, TypePath};
+use syn::{
+    parse_macro_input, Attribute, AttributeArgs, Block, Data, DeriveInput, Fields, FnArg, GenericArgument, ItemFn, Lit, Meta, NestedMeta,
+    Pat, PatType, Path, PathArguments, ReturnType, Type,
+};
 
 #[derive(Debug, FromMeta)]
 struct Args {
     usage: String,
     #[darling(default)]
     description: Option<String>,
+    /// Priority of the parameters which do not set their own with `#[arg(priority = ...)]`.
     #[darling(default)]
     priority: usize,
+    /// Permission checked with `Context::has_permission` before the command may be used.
+    #[darling(default)]
+    permission: Option<String>,
+    /// Path to a `fn(&Context) -> bool` which must return true for the command to be used.
+    #[darling(default)]
+    requires: Option<Path>,
+    /// Alternative names for the first literal of the usage,
+    /// given as `aliases("tp")` or `aliases = "tp"`.
+    #[darling(default)]
+    aliases: Aliases,
+    /// Namespace under which the command is also reachable as `namespace:name`.
+    #[darling(default)]
+    namespace: Option<String>,
+    /// Whether the command may share its path with other overloaded commands.
+    #[darling(default)]
+    overload: bool,
+}
+
+#[derive(Debug, Default)]
+struct Aliases(Vec<String>);
+
+impl FromMeta for Aliases {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Aliases(vec![value.to_owned()]))
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                NestedMeta::Lit(Lit::Str(alias)) => Ok(alias.value()),
+                item => Err(darling::Error::unexpected_type("non-string").with_span(item)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(Aliases)
+    }
+}
+
+/// Options for a single command parameter, given as `#[arg(...)]` on the parameter.
+#[derive(Debug, Default, FromMeta)]
+struct ParamArgs {
+    #[darling(default)]
+    suggestions: Option<Path>,
+    /// Parsers with a higher priority are tried first among their siblings.
+    #[darling(default)]
+    priority: Option<usize>,
+    /// Smallest accepted value of a numeric parameter,
+    /// given as a number or, if negative, a string.
+    #[darling(default)]
+    min: Option<Lit>,
+    /// Largest accepted value of a numeric parameter.
+    #[darling(default)]
+    max: Option<Lit>,
+}
+
+/// Options for a variant of an enum deriving `ArgumentKind`, given as `#[arg(...)]`.
+#[derive(Debug, Default, FromMeta)]
+struct VariantArgs {
+    /// Name of the variant in the input, instead of its name in snake case.
+    #[darling(default)]
+    rename: Option<String>,
+    /// Other names accepted for the variant.
+    #[darling(default)]
+    aliases: Aliases,
 }
 
 #[derive(Debug)]
@@ -22,8 +90,12 @@ struct Usage {
 
 #[derive(Debug)]
 enum Argument {
-    Parameter { name: String, priority: usize },
-    OptionalParameter { name: String, priority: usize },
+    /// A `<name>` parameter, or a `<name>...` parameter if `variadic`.
+    Parameter { name: String, variadic: bool },
+    OptionalParameter {
+        name: String,
+        default: Option<String>,
+    },
     Literal { value: String },
 }
 
@@ -41,7 +113,7 @@ pub fn provider(
     }
 
     if let None = input.sig.asyncness {
-        abort_call_site!("command must be an async fn");
+        abort_call_site!("provider must be an async fn");
     }
 
     if let Some(first_generic) = input.sig.generics.params.iter().next() {
@@ -49,48 +121,163 @@ pub fn provider(
             .as_type_param()
             .map(|type_param| format!("remove the parameter {}", type_param.ident));
         emit_error!(
-            first_generic.span(), "command functions may not have generic parameters";
+            first_generic.span(), "provider functions may not have generic parameters";
 
             help =? help;
         );
     }
 
-    let provider_ident = input.sig.ident;
+    let provider_ident = &input.sig.ident;
 
-    let impl_header = if let Some((ok, error)) = parse_result(input.sig.output) {
-        quote! {
-            impl<C> lieutenant::Provider for #provider_ident
-            where
-                C: Context<Ok = #ok, Error = #error>,
-        }
-    } else {
-        abort_call_site!("provider must have a return type!");
+    let output = match parse_result(&input.sig.output) {
+        Some(output) => output,
+        None => abort!(input.sig.output.span(), "provider must return a `Result`";
+
+            help = "change the return type to `Result<<provided type>, <error type>>`";
+        ),
     };
 
-    let params = input.sig.inputs;
+    let ctx_type = match input.sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+        [FnArg::Typed(ctx)] => match ctx.ty.as_ref() {
+            Type::Reference(reference) if reference.mutability.is_none() => &reference.elem,
+            ty => abort!(ty.span(), "context input must be a shared reference";
 
-    let block = input.block;
+                help = "change the type of the parameter to `&{}`", quote!(#ty);
+            ),
+        },
+        _ => abort!(input.sig.inputs.span(), "provider must take the context as its only parameter";
 
-    let ctx_type = quote! {};
-    let t = quote! {};
+            help = "change the parameters to `ctx: &<context type>`";
+        ),
+    };
 
     (quote! {
-        #impl_header {
-            fn provide<'a>(#params) -> Pin<Box<dyn Future<Output = Result<Self, C::Error>> + Send + Sync + 'a>> {
-                
+        #input
+
+        impl lieutenant::Provider<#ctx_type> for #output {
+            type Output = Self;
+
+            fn provide<'a>(
+                ctx: &'a #ctx_type,
+            ) -> std::pin::Pin<Box<dyn std::future::Future<
+                Output = Result<Self, <#ctx_type as lieutenant::Context>::Error>,
+            > + Send + 'a>> {
+                Box::pin(#provider_ident(ctx))
             }
         }
-    }).into()
+    })
+    .into()
+}
+
+/// Returns `T` if `output` is `Result<T, E>`.
+fn parse_result(output: &ReturnType) -> Option<&Type> {
+    let ty = match output {
+        ReturnType::Type(_, ty) => ty.as_ref(),
+        ReturnType::Default => return None,
+    };
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
-fn parse_result(output: ReturnType) -> Option<(TypePath, TypePath)> {
-    return None;
-    // let ReturnType::Type(_, kind) = output;
-    // let Type::Path(path) = *kind;
-    // let 
+/// Implements `ArgumentKind` for an enum of unit variants,
+/// which are matched by name in the input.
+#[proc_macro_error]
+#[proc_macro_derive(ArgumentKind, attributes(arg))]
+pub fn derive_argument_kind(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => abort_call_site!("#[derive(ArgumentKind)] is only supported on enums"),
+    };
+
+    if let Some(first_generic) = input.generics.params.iter().next() {
+        emit_error!(first_generic.span(), "argument enums may not have generic parameters");
+    }
+
+    // Aliases are accepted by `from_name`, but neither suggested nor listed as expected.
+    let mut names: Vec<String> = vec![];
+    let mut all_names: Vec<String> = vec![];
+    let mut arms = vec![];
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            emit_error!(
+                variant.fields.span(), "the variant {} has fields", variant.ident;
+
+                help = "only unit variants can be parsed by name";
+            );
+            continue;
+        }
+
+        let args: VariantArgs = parse_arg_attrs(&variant.attrs, variant.span());
+        let name = args
+            .rename
+            .unwrap_or_else(|| snake_case(&variant.ident.to_string()));
+
+        names.push(name.clone());
+        let mut variant_names = vec![name];
+        variant_names.extend(args.aliases.0);
+        for name in &variant_names {
+            if all_names.contains(name) {
+                emit_error!(variant.span(), "the name `{}` is used by more than one variant", name);
+            }
+            all_names.push(name.clone());
+        }
+
+        let ident = &variant.ident;
+        arms.push(quote! {
+            #(#variant_names)|* => Some(Self::#ident),
+        });
+    }
 
-    abort_call_site!("{:#?}", output);
-    unimplemented!()
+    let ident = &input.ident;
+
+    (quote! {
+        impl<C: lieutenant::Context> lieutenant::ArgumentKind<C> for #ident {
+            type Checker = lieutenant::parsers::EnumChecker<Self>;
+        }
+
+        impl lieutenant::parsers::EnumArgument for #ident {
+            const NAMES: &'static [&'static str] = &[#(#names),*];
+
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #(#arms)*
+                    _ => None,
+                }
+            }
+        }
+    })
+    .into()
+}
+
+/// Converts a variant name such as `GameMode` to `game_mode`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
 }
 
 #[proc_macro_error]
@@ -127,7 +314,10 @@ pub fn command(
     let usage = parse_usage(&args.usage);
     let parameters = collect_parameters(&usage, &input.sig.inputs.iter());
 
+    validate_greedy_parameters(&usage, &parameters);
+
     let ctx_type = detect_context_type(&parameters, input.sig.inputs.iter().next());
+    let provided = collect_provided_parameters(&parameters, ctx_type, input.sig.inputs.iter());
 
     let command_ident = &input.sig.ident;
 
@@ -136,8 +326,11 @@ pub fn command(
             impl lieutenant::Command<#ctx_type> for #command_ident
         }
     } else {
+        let provided_types = provided.iter().map(|parameter| &parameter.ty);
         quote! {
             impl <C: Context> lieutenant::Command<C> for #command_ident
+            where
+                #(#provided_types: lieutenant::Provider<C>,)*
         }
     };
 
@@ -149,8 +342,9 @@ pub fn command(
 
     let command_spec = generate_command_spec(
         &usage,
-        args.description,
+        &args,
         &parameters,
+        &provided,
         ctx_type,
         &input.block,
     );
@@ -173,23 +367,68 @@ fn parse_usage(usage: &str) -> Usage {
     let mut arguments = vec![];
 
     for splitted in usage.split(' ') {
-        let (first, middle) = splitted.split_at(1.min(splitted.len()));
-        let (middle, last) = middle.split_at(middle.len() - 1);
+        let (parameter, variadic) = match splitted.strip_suffix("...") {
+            Some(parameter) if parameter.starts_with('<') => (parameter, true),
+            _ => (splitted, false),
+        };
+        let (first, middle) = parameter.split_at(1.min(parameter.len()));
+        let (middle, last) = middle.split_at(middle.len().saturating_sub(1));
         match (first, middle, last) {
             ("<", param, ">") => arguments.push(Argument::Parameter {
                 name: param.to_owned(),
-                priority: 0,
-            }),
-            ("[", param, "]") => arguments.push(Argument::OptionalParameter {
-                name: param.to_owned(),
-                priority: 0,
+                variadic,
             }),
+            ("[", param, "]") => {
+                let (name, default) = match param.find('=') {
+                    Some(i) => (&param[..i], Some(param[i + 1..].to_owned())),
+                    None => (param, None),
+                };
+                arguments.push(Argument::OptionalParameter {
+                    name: name.to_owned(),
+                    default,
+                })
+            }
             (_, _, _) => arguments.push(Argument::Literal {
                 value: splitted.to_owned(),
             }),
         }
     }
 
+    if let Some(optional) = arguments
+        .iter()
+        .position(|argument| matches!(argument, Argument::OptionalParameter { .. }))
+    {
+        if let Some(required) = arguments[optional..]
+            .iter()
+            .find(|argument| !matches!(argument, Argument::OptionalParameter { .. }))
+        {
+            let required = match required {
+                Argument::Parameter { name, .. } => format!("<{}>", name),
+                Argument::Literal { value } => value.clone(),
+                Argument::OptionalParameter { .. } => unreachable!(),
+            };
+            emit_call_site_error!(
+                "`{}` follows an optional parameter", required;
+
+                help = "optional parameters may only appear at the end of the usage";
+            );
+        }
+    }
+
+    // A variadic parameter reads values up to the next literal,
+    // so no other parameter may directly follow it.
+    for pair in arguments.windows(2) {
+        if let [Argument::Parameter { name, variadic: true }, next] = pair {
+            if !matches!(next, Argument::Literal { .. }) {
+                emit_call_site_error!(
+                    "the variadic parameter {} is followed by another parameter", name;
+
+                    help = "separate the parameters with a literal or move `<{}>...` to the end of the usage", name;
+                );
+            }
+        }
+    }
+
     Usage { arguments }
 }
 
@@ -234,21 +473,186 @@ fn collect_parameter<'a>(
 fn validate_parameter(name: &str, arg: &Argument, arg_type: &PatType) {
     // If not an optional parameter, ensure the type is not an option.
     // Otherwise, ensure it _is_ an Option.
-    if let Argument::Parameter { .. } = arg {
+    if let Argument::Parameter { variadic, .. } = arg {
         // not optional
         validate_argument_type(&arg_type.ty, name);
-        if let Type::Path(path) = arg_type.ty.as_ref() {
-            // verify that path is not an `Option`
-            if path.path.is_ident(&Ident::new("Option", Span::call_site())) {
-                emit_error!(
-                    path.span(), "the parameter {} is defined as an `Option`, but the usage message indicates it is a required argument", name;
+        let ty = &arg_type.ty;
+        match (variadic, vec_inner_type(ty)) {
+            (true, None) => emit_error!(
+                ty.span(), "the parameter {} is variadic in the usage message, but is not defined as a `Vec`", name;
+
+                help = "change the type of the parameter to `Vec<{}>`", quote!(#ty);
+            ),
+            (false, Some(_)) => emit_error!(
+                ty.span(), "the parameter {} is defined as a `Vec`, but the usage message does not indicate it is variadic", name;
+
+                help = "change the usage instructions to make the argument variadic: `<{}>...`", name;
+            ),
+            _ => (),
+        }
+        if option_inner_type(&arg_type.ty).is_some() {
+            emit_error!(
+                arg_type.ty.span(), "the parameter {} is defined as an `Option`, but the usage message indicates it is a required argument", name;
 
-                    help = "change the usage instructions to make the argument optional: `<{}>`", name;
-                );
-            }
-        };
+                help = "change the usage instructions to make the argument optional: `[{}]`", name;
+            );
+        }
+    } else if let Argument::OptionalParameter {
+        default: Some(default),
+        ..
+    } = arg
+    {
+        // optional with a default value
+        let ty = &arg_type.ty;
+        validate_argument_type(ty, name);
+        if option_inner_type(ty).is_some() {
+            emit_error!(
+                ty.span(), "the parameter {} has a default value, but is defined as an `Option`", name;
+
+                help = "remove the `Option` around the type of the parameter";
+            );
+        } else if !default_parses(ty, default) {
+            emit_error!(
+                ty.span(), "the default value `{}` of the parameter {} is not a valid `{}`", default, name, quote!(#ty)
+            );
+        }
     } else {
         // optional
+        let ty = &arg_type.ty;
+        match option_inner_type(ty) {
+            Some(inner) => validate_argument_type(inner, name),
+            None => emit_error!(
+                ty.span(), "the parameter {} is optional in the usage message, but is not defined as an `Option`", name;
+
+                help = "change the type of the parameter to `Option<{}>`", quote!(#ty);
+            ),
+        }
+    }
+}
+
+/// Checks at compile time whether `default` parses as `ty`, if `ty` is a primitive.
+/// Other types are parsed by their `ArgumentChecker` when the command is registered.
+fn default_parses(ty: &Type, default: &str) -> bool {
+    let ident = match ty {
+        Type::Path(path) => match path.path.get_ident() {
+            Some(ident) => ident.to_string(),
+            None => return true,
+        },
+        _ => return true,
+    };
+
+    macro_rules! parses {
+        ($($ty:ident,)*) => {
+            match ident.as_str() {
+                $(stringify!($ty) => default.parse::<$ty>().is_ok(),)*
+                _ => true,
+            }
+        };
+    }
+
+    parses!(
+        i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char,
+    )
+}
+
+/// Checks at compile time whether `default` lies within the bounds given with
+/// `#[arg(min = ..., max = ...)]`, if `ty` is a primitive number.
+fn default_in_bounds(ty: &Type, default: &str, min: Option<&Lit>, max: Option<&Lit>) -> bool {
+    let ident = match ty {
+        Type::Path(path) => match path.path.get_ident() {
+            Some(ident) => ident.to_string(),
+            None => return true,
+        },
+        _ => return true,
+    };
+    let bound = |lit: Option<&Lit>| match lit {
+        Some(Lit::Int(value)) => Some(value.base10_digits().to_owned()),
+        Some(Lit::Float(value)) => Some(value.base10_digits().to_owned()),
+        Some(Lit::Str(value)) => Some(value.value()),
+        _ => None,
+    };
+    let (min, max) = (bound(min), bound(max));
+
+    macro_rules! in_bounds {
+        ($($ty:ident,)*) => {
+            match ident.as_str() {
+                $(stringify!($ty) => match default.parse::<$ty>() {
+                    Ok(value) => {
+                        let parse = |bound: &Option<String>| bound.as_ref()?.parse::<$ty>().ok();
+                        parse(&min).iter().all(|min| value >= *min)
+                            && parse(&max).iter().all(|max| value <= *max)
+                    }
+                    Err(_) => true,
+                },)*
+                _ => true,
+            }
+        };
+    }
+
+    in_bounds!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64,)
+}
+
+/// Ensures that only the last argument of the usage is a `Greedy` parameter,
+/// since it consumes the rest of the input.
+fn validate_greedy_parameters(usage: &Usage, parameters: &[&PatType]) {
+    let mut parameters = parameters.iter();
+    for (i, argument) in usage.arguments.iter().enumerate() {
+        let name = match argument {
+            Argument::Parameter { name, .. } | Argument::OptionalParameter { name, .. } => name,
+            Argument::Literal { .. } => continue,
+        };
+        let parameter = match parameters.next() {
+            Some(parameter) => parameter,
+            None => return,
+        };
+
+        if i + 1 < usage.arguments.len() && is_greedy(argument_kind_type(argument, parameter)) {
+            emit_error!(
+                parameter.ty.span(), "the greedy parameter {} consumes the rest of the input, but is not the last argument", name;
+
+                help = "move `<{}>` to the end of the usage", name;
+            );
+        }
+    }
+}
+
+fn is_greedy(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => {
+            matches!(path.path.segments.last(), Some(segment) if segment.ident == "Greedy")
+        }
+        _ => false,
+    }
+}
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    generic_inner_type(ty, "Option")
+}
+
+/// Returns `T` if `ty` is `Vec<T>`.
+fn vec_inner_type(ty: &Type) -> Option<&Type> {
+    generic_inner_type(ty, "Vec")
+}
+
+/// Returns `T` if `ty` is `<wrapper><T>`.
+fn generic_inner_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first()? {
+                GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
     }
 }
 
@@ -273,6 +677,30 @@ fn validate_argument_type(ty: &Type, name: &str) {
     }
 }
 
+fn parse_param_args(parameter: &PatType) -> ParamArgs {
+    parse_arg_attrs(&parameter.attrs, parameter.span())
+}
+
+/// Parses the `#[arg(...)]` attributes among `attrs` into `T`.
+fn parse_arg_attrs<T: FromMeta + Default>(attrs: &[Attribute], span: Span) -> T {
+    let mut nested: Vec<NestedMeta> = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("arg")) {
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => nested.extend(list.nested),
+            Ok(meta) => emit_error!(meta.span(), "expected `#[arg(...)]`"),
+            Err(e) => emit_error!(e.span(), "{}", e),
+        }
+    }
+
+    match T::from_list(&nested) {
+        Ok(args) => args,
+        Err(e) => {
+            emit_error!(span, "invalid parameters passed to #[arg]: {}", e);
+            T::default()
+        }
+    }
+}
+
 fn find_corresponding_arg<'a>(
     name: &str,
     args: &(impl Iterator<Item = &'a FnArg> + Clone),
@@ -342,10 +770,52 @@ fn detect_context_type<'a>(
         })
 }
 
+/// Returns the expression for a bound given with `#[arg(min = ...)]` or `#[arg(max = ...)]`.
+fn bound(lit: Option<&Lit>) -> TokenStream {
+    match lit {
+        None => quote! { None },
+        Some(Lit::Int(_)) | Some(Lit::Float(_)) => quote! { Some(#lit) },
+        Some(Lit::Str(value)) => match syn::parse_str::<syn::Expr>(&value.value()) {
+            Ok(expr) => quote! { Some(#expr) },
+            Err(e) => abort!(value.span(), "invalid bound: {}", e),
+        },
+        Some(lit) => abort!(lit.span(), "bounds must be numbers"),
+    }
+}
+
+/// Returns the function parameters which are neither the context nor named
+/// in the usage. Their values are obtained from their `Provider`.
+fn collect_provided_parameters<'a>(
+    parameters: &[&PatType],
+    ctx_type: Option<(&Type, &Pat)>,
+    inputs: impl Iterator<Item = &'a FnArg>,
+) -> Vec<&'a PatType> {
+    inputs
+        .skip(if ctx_type.is_some() { 1 } else { 0 })
+        .filter_map(|arg| match arg {
+            FnArg::Typed(arg) => Some(arg),
+            FnArg::Receiver(_) => None,
+        })
+        .filter(|arg| !parameters.iter().any(|param| param.pat == arg.pat))
+        .collect()
+}
+
+/// Returns the type implementing `ArgumentKind` for a parameter,
+/// which is `T` for optional parameters of type `Option<T>`.
+fn argument_kind_type<'a>(argument: &Argument, parameter: &'a PatType) -> &'a Type {
+    match argument {
+        Argument::OptionalParameter { default: None, .. } => {
+            option_inner_type(&parameter.ty).unwrap_or(&parameter.ty)
+        }
+        _ => &parameter.ty,
+    }
+}
+
 fn generate_command_spec(
     usage: &Usage,
-    description: Option<String>,
+    args: &Args,
     parameters: &[&PatType],
+    provided: &[&PatType],
     ctx_type: Option<(&Type, &Pat)>,
     block: &Block,
 ) -> TokenStream {
@@ -359,21 +829,74 @@ fn generate_command_spec(
     let mut arguments = vec![];
 
     let mut i = 0;
-    for argument in &usage.arguments {
+    for argument in usage.arguments.iter() {
         let argument = match argument {
-            Argument::Parameter { name, priority }
-            | Argument::OptionalParameter { name, priority } => {
+            Argument::Parameter { name, .. } | Argument::OptionalParameter { name, .. } => {
                 let argument_type = parameters[i];
-
-                let ty = &argument_type.ty;
+                let param_args = parse_param_args(argument_type);
+
+                let optional = matches!(argument, Argument::OptionalParameter { .. });
+                let ty = argument_kind_type(argument, argument_type);
+                let default = match argument {
+                    Argument::OptionalParameter {
+                        default: Some(default),
+                        ..
+                    } => quote! { Some(#default.into()) },
+                    _ => quote! { None },
+                };
                 i += 1;
 
+                let priority = param_args.priority.unwrap_or(args.priority);
+
+                let suggestions = match param_args.suggestions {
+                    Some(path) => quote! {
+                        Some(|ctx, partial| Box::pin(#path(ctx, partial)))
+                    },
+                    None => quote! { None },
+                };
+
+                let bounded = param_args.min.is_some() || param_args.max.is_some();
+                let checker = if let Argument::Parameter { variadic: true, .. } = argument {
+                    if bounded {
+                        emit_error!(argument_type.span(), "variadic parameters may not have bounds");
+                    }
+                    let element = vec_inner_type(ty).unwrap_or(ty);
+                    quote! {
+                        lieutenant::parsers::VecChecker::<#element>::default()
+                    }
+                } else if bounded {
+                    if let Argument::OptionalParameter {
+                        default: Some(default),
+                        ..
+                    } = argument
+                    {
+                        let (min, max) = (param_args.min.as_ref(), param_args.max.as_ref());
+                        if !default_in_bounds(ty, default, min, max) {
+                            emit_error!(
+                                argument_type.ty.span(), "the default value `{}` of the parameter {} is outside of its bounds", default, name
+                            );
+                        }
+                    }
+                    let min = bound(param_args.min.as_ref());
+                    let max = bound(param_args.max.as_ref());
+                    quote! {
+                        lieutenant::parsers::BoundedChecker::<#ty>::new(#min, #max)
+                    }
+                } else {
+                    quote! {
+                        <<#ty as lieutenant::ArgumentKind<#ctx_param>>::Checker
+                            as lieutenant::ArgumentChecker<#ctx_param>>::default()
+                    }
+                };
+
                 quote! {
                     lieutenant::Argument::Parser {
                         name: #name.into(),
-                        checker: Box::new(<<#ty as lieutenant::ArgumentKind<#ctx_param>>::Checker
-                            as lieutenant::ArgumentChecker<#ctx_param>>::default()),
+                        checker: Box::new(#checker),
                         priority: #priority,
+                        optional: #optional,
+                        default: #default,
+                        suggestions: #suggestions,
                     }
                 }
             }
@@ -395,56 +918,105 @@ fn generate_command_spec(
 
     let mut i = 0;
     for argument in usage.arguments.iter() {
-        match argument {
-            Argument::Parameter { .. } | Argument::OptionalParameter { .. } => {
-                let parameter = parameters[i];
-                let ident = &parameter.pat;
-                let ty = &parameter.ty;
-                let ctx_ident = match ctx_type {
-                    Some((_, ident)) => quote! { #ident },
-                    None => quote! { _ctx },
-                };
-
-                parse_args.push(quote! {
-                    let #ident = <<#ty as lieutenant::ArgumentKind<#ctx_param>>::Parser
-                    as lieutenant::ArgumentParser<#ctx_param>>::default().parse(#ctx_ident, &mut args).await.unwrap();
-                });
+        let name = match argument {
+            Argument::Parameter { name, .. } | Argument::OptionalParameter { name, .. } => name,
+            Argument::Literal { .. } => continue,
+        };
+        let parameter = parameters[i];
+        let ident = &parameter.pat;
+        let ty = argument_kind_type(argument, parameter);
+        i += 1;
+
+        // The dispatcher has parsed every argument which was given,
+        // as well as the default values of omitted optional arguments.
+        let value = quote! { __context.take::<#ty>(#name) };
+        parse_args.push(match argument {
+            Argument::OptionalParameter { default: None, .. } => quote! {
+                let #ident = #value;
+            },
+            _ => quote! {
+                let #ident = #value.expect("argument not parsed during dispatch");
+            },
+        });
+    }
 
-                i += 1;
-            }
-            Argument::Literal { value } => parse_args.push(quote! {
-                let head = args.advance_until(" ");
-                debug_assert_eq!(head, #value);
-            }),
+    // Provided values are resolved before the arguments are bound, so that
+    // a failing provider ends the command before its body runs.
+    // Providers need a name for the context, so a context parameter which is
+    // not an identifier, such as `_`, is bound to `__ctx` and matched in the body.
+    let (ctx_ident, ctx_pat, ctx_binding) = match ctx_type {
+        Some((_, Pat::Ident(pat))) => {
+            let ident = &pat.ident;
+            (quote! { #ident }, quote! { #pat }, quote! {})
         }
-    }
+        Some((_, pat)) => (quote! { __ctx }, quote! { __ctx }, quote! { let #pat = __ctx; }),
+        None => (quote! { _ctx }, quote! { _ctx }, quote! {}),
+    };
+    let provide_args = provided.iter().map(|parameter| {
+        let pat = &parameter.pat;
+        let ty = &parameter.ty;
+        quote! {
+            let #pat: #ty = <#ty as lieutenant::Provider<#ctx_param>>::provide(&*#ctx_ident).await?;
+        }
+    });
+
+    let context_pat = if parse_args.is_empty() {
+        quote! { _context }
+    } else {
+        quote! { mut __context }
+    };
 
     let ctx_type = match ctx_type {
-        Some((t, name)) => quote! { #name: &mut #t },
-        None => quote! { _ctx: &mut C },
+        Some((t, _)) => quote! { #ctx_pat: &mut #t },
+        None => quote! { #ctx_pat: &mut C },
     };
 
-    let description = match description {
+    let description = match &args.description {
         Some(description) => quote! { Some(#description.into()) },
         None => quote! { None },
     };
 
+    let aliases = &args.aliases.0;
+
+    let namespace = match &args.namespace {
+        Some(namespace) => quote! { Some(#namespace.into()) },
+        None => quote! { None },
+    };
+
+    let requirement = match (&args.permission, &args.requires) {
+        (None, None) => quote! { None },
+        (Some(permission), None) => quote! {
+            Some(|ctx| lieutenant::Context::has_permission(ctx, #permission))
+        },
+        (None, Some(requires)) => quote! {
+            Some(|ctx| #requires(ctx))
+        },
+        (Some(permission), Some(requires)) => quote! {
+            Some(|ctx| lieutenant::Context::has_permission(ctx, #permission) && #requires(ctx))
+        },
+    };
+
+    let overload = args.overload;
+
     let arguments_len = arguments.len();
 
     let res = quote! {
-        use lieutenant::ParserUtil;
         let mut arguments = Vec::with_capacity(#arguments_len);
         #(#arguments)*
 
         lieutenant::CommandSpec {
             arguments,
             description: #description,
-            exec: |#ctx_type, args| Box::pin(async move {
-                use lieutenant::{ArgumentParser as _, ArgumentChecker as _};
-                let mut args = args;
+            aliases: vec![#(#aliases.into()),*],
+            namespace: #namespace,
+            requirement: #requirement,
+            overload: #overload,
+            exec: lieutenant::Executor::<#ctx_param>::Fn(|#ctx_type, #context_pat| Box::pin(async move {
+                #(#provide_args)*
+                #ctx_binding
                 #(#parse_args)*
                 #block
-            }),
+            })),
         }
     };
     res
@@ -0,0 +1,52 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Values of a command's arguments, parsed during dispatch
+/// and keyed by the names the command gave its arguments.
+///
+/// Omitted optional arguments are absent, unless they have a default value.
+#[derive(Clone, Default)]
+pub struct ParsedArguments {
+    values: Vec<(Cow<'static, str>, Arc<dyn Any + Send + Sync>)>,
+}
+
+impl ParsedArguments {
+    /// Creates an empty `ParsedArguments`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn insert(&mut self, name: Cow<'static, str>, value: Arc<dyn Any + Send + Sync>) {
+        self.values.push((name, value));
+    }
+
+    /// Returns whether the argument `name` has a value.
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.iter().any(|(key, _)| key == name)
+    }
+
+    /// Returns the value of the argument `name`, or `None`
+    /// if it has no value or its value is not a `T`.
+    pub fn get<T: Any>(&self, name: &str) -> Option<&T> {
+        self.values
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.downcast_ref())
+    }
+
+    /// Removes and returns the value of the argument `name`, or `None`
+    /// if it has no value or its value is not a `T`.
+    ///
+    /// The value is cloned if it is shared with other commands
+    /// which were tried during the same dispatch.
+    pub fn take<T: Any + Clone + Send + Sync>(&mut self, name: &str) -> Option<T> {
+        let index = self
+            .values
+            .iter()
+            .position(|(key, value)| key == name && value.is::<T>())?;
+        let (_, value) = self.values.swap_remove(index);
+        let value = value.downcast::<T>().ok()?;
+        Some(Arc::try_unwrap(value).unwrap_or_else(|value| (*value).clone()))
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    Argument, ArgumentChecker, ArgumentKind, CommandContext, CommandSpec, Context, ExecFuture,
+    Executor, Requirement, Suggest,
+};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// Creates a node matching the literal `value`.
+pub fn literal<C: Context>(value: impl Into<Cow<'static, str>>) -> ArgumentBuilder<C> {
+    ArgumentBuilder::new(Argument::Literal {
+        value: value.into(),
+    })
+}
+
+/// Creates a node parsing an argument of type `T`, e.g. `argument::<i32>("x")`.
+pub fn argument<T>(name: impl Into<Cow<'static, str>>) -> ParserBuilder<T> {
+    ParserBuilder {
+        name: name.into(),
+        priority: 0,
+        _phantom: PhantomData,
+    }
+}
+
+/// Builder of a node parsing an argument of type `T`, returned by `argument`.
+///
+/// It becomes an `ArgumentBuilder` once the type of the context is known,
+/// so that `argument::<T>` needs no type annotation for the context.
+pub struct ParserBuilder<T> {
+    name: Cow<'static, str>,
+    priority: usize,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> ParserBuilder<T> {
+    /// Sets the priority of this argument among its siblings.
+    pub fn priority(mut self, value: usize) -> Self {
+        self.priority = value;
+        self
+    }
+
+    /// See `ArgumentBuilder::then`.
+    pub fn then<C>(self, child: impl Into<ArgumentBuilder<C>>) -> ArgumentBuilder<C>
+    where
+        C: Context,
+        T: ArgumentKind<C>,
+    {
+        ArgumentBuilder::from(self).then(child)
+    }
+
+    /// See `ArgumentBuilder::executes`.
+    pub fn executes<C, F>(self, exec: F) -> ArgumentBuilder<C>
+    where
+        C: Context,
+        T: ArgumentKind<C>,
+        F: for<'a> Fn(&'a mut C, CommandContext<'a>) -> ExecFuture<'a, C> + Send + Sync + 'static,
+    {
+        ArgumentBuilder::from(self).executes(exec)
+    }
+
+    /// See `ArgumentBuilder::description`.
+    pub fn description<C>(self, description: impl Into<Cow<'static, str>>) -> ArgumentBuilder<C>
+    where
+        C: Context,
+        T: ArgumentKind<C>,
+    {
+        ArgumentBuilder::from(self).description(description)
+    }
+
+    /// See `ArgumentBuilder::requires`.
+    pub fn requires<C>(self, requirement: Requirement<C>) -> ArgumentBuilder<C>
+    where
+        C: Context,
+        T: ArgumentKind<C>,
+    {
+        ArgumentBuilder::from(self).requires(requirement)
+    }
+
+    /// See `ArgumentBuilder::suggests`.
+    pub fn suggests<C>(self, suggest: Suggest<C>) -> ArgumentBuilder<C>
+    where
+        C: Context,
+        T: ArgumentKind<C>,
+    {
+        ArgumentBuilder::from(self).suggests(suggest)
+    }
+}
+
+impl<C, T> From<ParserBuilder<T>> for ArgumentBuilder<C>
+where
+    C: Context,
+    T: ArgumentKind<C>,
+{
+    fn from(parser: ParserBuilder<T>) -> Self {
+        ArgumentBuilder::new(Argument::Parser {
+            name: parser.name,
+            checker: Box::new(<T::Checker as ArgumentChecker<C>>::default()),
+            priority: parser.priority,
+            optional: false,
+            default: None,
+            suggestions: None,
+        })
+    }
+}
+
+/// Builds a tree of commands at runtime, as an alternative to `#[command]`.
+///
+/// Every node with an executor becomes a command whose arguments are the
+/// nodes leading up to it:
+///
+/// ```ignore
+/// let gamemode = literal("gamemode")
+///     .then(argument::<String>("mode").executes(set_gamemode))
+///     .then(literal("query").executes(query_gamemode));
+/// dispatcher.register_tree(gamemode)?;
+/// ```
+pub struct ArgumentBuilder<C: Context> {
+    argument: Argument<C>,
+    children: Vec<ArgumentBuilder<C>>,
+    exec: Option<Executor<C>>,
+    description: Option<Cow<'static, str>>,
+    requirement: Option<Requirement<C>>,
+}
+
+impl<C: Context> ArgumentBuilder<C> {
+    fn new(argument: Argument<C>) -> Self {
+        Self {
+            argument,
+            children: Vec::new(),
+            exec: None,
+            description: None,
+            requirement: None,
+        }
+    }
+
+    /// Adds a node which may follow this one.
+    pub fn then(mut self, child: impl Into<ArgumentBuilder<C>>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// Makes the input ending at this node a command executed by `exec`.
+    ///
+    /// The arguments are available from the `CommandContext` under the names
+    /// given to `argument`, e.g. `context.get::<i32>("x")`.
+    pub fn executes<F>(mut self, exec: F) -> Self
+    where
+        F: for<'a> Fn(&'a mut C, CommandContext<'a>) -> ExecFuture<'a, C> + Send + Sync + 'static,
+    {
+        self.exec = Some(Executor::closure(exec));
+        self
+    }
+
+    /// Sets the description of the command executed at this node.
+    pub fn description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Hides the commands at and below this node from contexts which
+    /// do not meet `requirement`, unless a node below sets its own.
+    pub fn requires(mut self, requirement: Requirement<C>) -> Self {
+        self.requirement = Some(requirement);
+        self
+    }
+
+    /// Sets the priority of this argument among its siblings.
+    /// Has no effect on literals.
+    pub fn priority(mut self, value: usize) -> Self {
+        if let Argument::Parser { priority, .. } = &mut self.argument {
+            *priority = value;
+        }
+        self
+    }
+
+    /// Overrides the suggestions of this argument's checker.
+    /// Has no effect on literals.
+    pub fn suggests(mut self, suggest: Suggest<C>) -> Self {
+        if let Argument::Parser { suggestions, .. } = &mut self.argument {
+            *suggestions = Some(suggest);
+        }
+        self
+    }
+
+    /// Returns the commands of this tree, in depth-first order.
+    pub fn build(self) -> Vec<CommandSpec<C>> {
+        let mut specs = Vec::new();
+        self.build_into(&mut Vec::new(), None, &mut specs);
+        specs
+    }
+
+    fn build_into(
+        self,
+        arguments: &mut Vec<Argument<C>>,
+        requirement: Option<Requirement<C>>,
+        specs: &mut Vec<CommandSpec<C>>,
+    ) {
+        let requirement = self.requirement.or(requirement);
+        arguments.push(self.argument);
+
+        if let Some(exec) = self.exec {
+            specs.push(CommandSpec {
+                arguments: arguments.clone(),
+                description: self.description,
+                aliases: Vec::new(),
+                namespace: None,
+                requirement,
+                overload: false,
+                exec,
+            });
+        }
+
+        for child in self.children {
+            child.build_into(arguments, requirement, specs);
+        }
+
+        arguments.pop();
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{ArgumentChecker, Context};
+use crate::{ArgumentChecker, CommandContext, Context};
 use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 
 pub trait Command<C: Context> {
     /// Returns the root node for parsing this command.
@@ -16,7 +18,16 @@ pub enum Argument<C: Context> {
     Parser {
         name: Cow<'static, str>,
         checker: Box<dyn ArgumentChecker<C>>,
+        /// Parsers with a higher priority are tried first
+        /// among the siblings of a node.
         priority: usize,
+        /// Whether the argument may be omitted. Only trailing
+        /// arguments of a command may be optional.
+        optional: bool,
+        /// Value used when an optional argument is omitted.
+        default: Option<Cow<'static, str>>,
+        /// Overrides the suggestions of `checker`.
+        suggestions: Option<Suggest<C>>,
     },
 }
 
@@ -27,6 +38,22 @@ impl<C: Context> Argument<C> {
             Argument::Parser { priority, .. } => *priority,
         }
     }
+
+    pub fn is_optional(&self) -> bool {
+        match self {
+            Argument::Literal { .. } => false,
+            Argument::Parser { optional, .. } => *optional,
+        }
+    }
+
+    /// Returns inputs which this argument accepts: the value of a
+    /// literal or the examples of a parser's checker.
+    pub fn examples(&self) -> Vec<Cow<'static, str>> {
+        match self {
+            Argument::Literal { value } => vec![value.clone()],
+            Argument::Parser { checker, .. } => checker.examples(),
+        }
+    }
 }
 
 impl<C: Context> Clone for Argument<C> {
@@ -39,15 +66,48 @@ impl<C: Context> Clone for Argument<C> {
                 name,
                 checker,
                 priority,
+                optional,
+                default,
+                suggestions,
             } => Argument::Parser {
                 name: name.clone(),
                 checker: checker.box_clone(),
                 priority: *priority,
+                optional: *optional,
+                default: default.clone(),
+                suggestions: *suggestions,
             },
         }
     }
 }
 
+/// Formats the argument as it appears in a usage message,
+/// e.g. `gamemode`, `<mode>`, `<players>...`, `[player]` or `[count=1]`.
+impl<C: Context> fmt::Display for Argument<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Argument::Literal { value } => f.write_str(value),
+            Argument::Parser {
+                name,
+                checker,
+                optional,
+                default,
+                ..
+            } => {
+                match (optional, default) {
+                    (false, _) => write!(f, "<{}>", name)?,
+                    (true, Some(default)) => write!(f, "[{}={}]", name, default)?,
+                    (true, None) => write!(f, "[{}]", name)?,
+                }
+                if checker.is_variadic() {
+                    f.write_str("...")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 impl<C: Context> PartialEq for Argument<C>
 where
     C: Context,
@@ -56,7 +116,7 @@ where
         match (self, other) {
             (Argument::Literal { value }, Argument::Literal { value: other }) => value == other,
             (Argument::Parser { checker, .. }, Argument::Parser { checker: other, .. }) => {
-                checker.equals(other)
+                checker.key() == other.key()
             }
             (_, _) => false,
         }
@@ -74,26 +134,104 @@ where
     }
 }
 
+/// Orders arguments by the precedence with which they are tried during dispatch:
+/// literals are greater than parsers, and parsers are ordered by priority.
 impl<C: Context> Ord for Argument<C>
 where
     C: 'static,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.priority().cmp(&other.priority())
+        match (self, other) {
+            (Argument::Literal { .. }, Argument::Literal { .. }) => Ordering::Equal,
+            (Argument::Literal { .. }, Argument::Parser { .. }) => Ordering::Greater,
+            (Argument::Parser { .. }, Argument::Literal { .. }) => Ordering::Less,
+            (_, _) => self.priority().cmp(&other.priority()),
+        }
+    }
+}
+
+/// Future returned by the handler of a command.
+pub type ExecFuture<'a, C> =
+    Pin<Box<dyn Future<Output = Result<<C as Context>::Ok, <C as Context>::Error>> + Send + 'a>>;
+
+/// Handler of a command, called with the context and what was parsed from the input.
+pub type Exec<C> = for<'a> fn(&'a mut C, CommandContext<'a>) -> ExecFuture<'a, C>;
+
+type ExecClosure<C> =
+    dyn for<'a> Fn(&'a mut C, CommandContext<'a>) -> ExecFuture<'a, C> + Send + Sync;
+
+/// Handler of a command: either a function pointer, as generated by `#[command]`,
+/// or a closure which may capture state such as configuration or a database handle.
+pub enum Executor<C: Context> {
+    Fn(Exec<C>),
+    Closure(Arc<ExecClosure<C>>),
+}
+
+impl<C: Context> Executor<C> {
+    /// Creates an executor from a closure.
+    pub fn closure<F>(f: F) -> Self
+    where
+        F: for<'a> Fn(&'a mut C, CommandContext<'a>) -> ExecFuture<'a, C> + Send + Sync + 'static,
+    {
+        Executor::Closure(Arc::new(f))
+    }
+
+    /// Runs the handler.
+    pub fn call<'a>(&self, ctx: &'a mut C, context: CommandContext<'a>) -> ExecFuture<'a, C> {
+        match self {
+            Executor::Fn(exec) => exec(ctx, context),
+            Executor::Closure(exec) => exec(ctx, context),
+        }
+    }
+}
+
+impl<C: Context> Clone for Executor<C> {
+    fn clone(&self) -> Self {
+        match self {
+            Executor::Fn(exec) => Executor::Fn(*exec),
+            Executor::Closure(exec) => Executor::Closure(Arc::clone(exec)),
+        }
     }
 }
 
-pub type Exec<C> = for<'a> fn(
-    &'a mut C,
-    &'a str,
-) -> Pin<
-    Box<dyn Future<Output = Result<<C as Context>::Ok, <C as Context>::Error>> + Send + 'a>,
->;
+impl<C: Context> From<Exec<C>> for Executor<C> {
+    fn from(exec: Exec<C>) -> Self {
+        Executor::Fn(exec)
+    }
+}
+
+/// Provides completions for a partially typed argument.
+pub type Suggest<C> =
+    for<'a> fn(&'a C, &'a str) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + 'a>>;
+
+/// Predicate deciding whether a context may use a command.
+pub type Requirement<C> = fn(&C) -> bool;
 
 pub struct CommandSpec<C: Context> {
     pub arguments: Vec<Argument<C>>,
     pub description: Option<Cow<'static, str>>,
-    pub exec: Exec<C>,
+    /// Alternative names for the first argument, which must be a literal.
+    pub aliases: Vec<Cow<'static, str>>,
+    /// Namespace, such as the name of a plugin, under which the command
+    /// is registered as `namespace:name`. The first argument must be a literal.
+    pub namespace: Option<Cow<'static, str>>,
+    /// If set, the command is hidden from contexts which do not meet it.
+    pub requirement: Option<Requirement<C>>,
+    /// Allows the command to be executed at the same node as other overloaded
+    /// commands. Their handlers are tried in registration order until one succeeds.
+    pub overload: bool,
+    pub exec: Executor<C>,
+}
+
+impl<C: Context> CommandSpec<C> {
+    /// Returns the usage message of this command, e.g. `give <item> [count=1]`.
+    pub fn usage(&self) -> String {
+        self.arguments
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 impl<C: Context> Command<C> for CommandSpec<C> {
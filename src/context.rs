@@ -0,0 +1,71 @@
+use crate::ParsedArguments;
+use std::any::Any;
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Node of the command graph which matched part of the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchedNode {
+    /// Value of a literal or name of an argument, as given by the command.
+    pub name: Cow<'static, str>,
+    /// Whether the node is a literal.
+    pub literal: bool,
+    /// Range of the node's token in the input.
+    pub range: Range<usize>,
+}
+
+/// Input of a command's executor: the dispatched input
+/// and what the dispatcher parsed from it.
+pub struct CommandContext<'a> {
+    input: &'a str,
+    path: Vec<MatchedNode>,
+    arguments: ParsedArguments,
+}
+
+impl<'a> CommandContext<'a> {
+    pub fn new(input: &'a str, path: Vec<MatchedNode>, arguments: ParsedArguments) -> Self {
+        Self {
+            input,
+            path,
+            arguments,
+        }
+    }
+
+    /// Returns the whole input which was dispatched.
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    /// Returns the nodes which matched the input, in order.
+    ///
+    /// Omitted optional arguments are not part of the path.
+    pub fn path(&self) -> &[MatchedNode] {
+        &self.path
+    }
+
+    /// Returns the range in the input of the argument `name`,
+    /// or `None` if it was omitted.
+    pub fn range(&self, name: &str) -> Option<Range<usize>> {
+        self.path
+            .iter()
+            .find(|node| !node.literal && node.name == name)
+            .map(|node| node.range.clone())
+    }
+
+    /// Returns the value of the argument `name`, or `None`
+    /// if it has no value or its value is not a `T`.
+    pub fn get<T: Any>(&self, name: &str) -> Option<&T> {
+        self.arguments.get(name)
+    }
+
+    /// Removes and returns the value of the argument `name`, or `None`
+    /// if it has no value or its value is not a `T`.
+    pub fn take<T: Any + Clone + Send + Sync>(&mut self, name: &str) -> Option<T> {
+        self.arguments.take(name)
+    }
+
+    /// Returns the values of the command's arguments.
+    pub fn arguments(&self) -> &ParsedArguments {
+        &self.arguments
+    }
+}
@@ -1,14 +1,58 @@
-use crate::{command::Exec, Argument, Command, CommandSpec, Context, ParserUtil};
+use crate::{
+    Argument, ArgumentBuilder, Command, CommandContext, CommandSpec, Context, DispatchError,
+    MatchedNode, ParsedArguments, StringReader, Suggestions,
+};
 use slab::Slab;
 use smallvec::SmallVec;
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub enum RegisterError {
     /// Overlapping commands exist: two commands
-    /// have an executable node at the same point.
+    /// have an executable node at the same point
+    /// and not both of them allow overloads.
     OverlappingCommands,
     /// Attempted to register an executable command at the root of the command graph.
     ExecutableRoot,
+    /// Attempted to alias a command which does not start with a literal.
+    AliasedParser,
+    /// An alias is already registered as a command or as the bare name
+    /// of namespaced commands, or a command was registered under
+    /// a name which is already an alias.
+    AliasConflict,
+    /// Attempted to namespace a command which does not start with a literal.
+    NamespacedParser,
+    /// The default value of the optional argument `name` is not accepted by its checker.
+    InvalidDefault { name: Cow<'static, str> },
+}
+
+/// Pair of sibling arguments which can both accept the same input,
+/// as reported by `CommandDispatcher::find_ambiguities`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ambiguity {
+    /// Arguments leading up to the siblings, e.g. `teleport <x>`.
+    /// Empty for root arguments.
+    pub path: String,
+    /// The sibling tried first during dispatch, formatted as in a usage message.
+    pub first: String,
+    /// The sibling tried second during dispatch, formatted as in a usage message.
+    pub second: String,
+    /// Example inputs accepted by both arguments.
+    pub inputs: Vec<String>,
+}
+
+/// Identifies a registered command, used to unregister it.
+///
+/// A handle stays unique after its command is unregistered,
+/// even though the command's slot is reused.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CommandHandle {
+    index: usize,
+    generation: u64,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -25,7 +69,11 @@ impl std::ops::Deref for NodeKey {
 pub struct CommandDispatcher<C: Context> {
     nodes: Slab<Node<C>>,
     children: SmallVec<[NodeKey; 4]>,
-    commands: Vec<CommandSpec<C>>,
+    commands: Slab<RegisteredCommand<C>>,
+    /// Bare names of namespaced commands, with the namespace they should resolve to.
+    bare_names: HashMap<String, Option<String>>,
+    /// Generation of the next registered command.
+    generation: u64,
 }
 
 impl<C: Context> Default for CommandDispatcher<C> {
@@ -34,6 +82,8 @@ impl<C: Context> Default for CommandDispatcher<C> {
             nodes: Default::default(),
             children: Default::default(),
             commands: Default::default(),
+            bare_names: Default::default(),
+            generation: 0,
         }
     }
 }
@@ -48,59 +98,308 @@ where
     }
 
     /// Registers a command to this `CommandDispatcher`.
-    pub fn register(&mut self, command: impl Command<C>) -> Result<(), RegisterError>
+    ///
+    /// Fails without modifying the dispatcher if the command could be executed
+    /// at the same node as an already registered command, unless both commands
+    /// are marked as `overload`.
+    ///
+    /// A command with a namespace, e.g. `plugin`, is registered as `plugin:kill`.
+    /// Its bare name `kill` resolves to the namespace passed to `prefer_namespace`
+    /// or, by default, the first namespace registered with that name, unless a
+    /// command without a namespace is registered as `kill`, before or after.
+    ///
+    /// Returns a handle which can be passed to `unregister` to remove the command.
+    pub fn register(&mut self, command: impl Command<C>) -> Result<CommandHandle, RegisterError>
     where
         C: 'static,
     {
-        let spec = command.build();
+        let mut spec = command.build();
+
+        if spec.arguments.is_empty() {
+            // Command with zero arguments?
+            return Err(RegisterError::ExecutableRoot);
+        }
+
+        // Variadic arguments stop reading values at the literal which follows.
+        for i in 1..spec.arguments.len() {
+            let (previous, next) = spec.arguments.split_at_mut(i);
+            if let (Argument::Parser { checker, .. }, Argument::Literal { value }) =
+                (&mut previous[i - 1], &next[0])
+            {
+                if let Some(terminated) = checker.until_literal(value) {
+                    *checker = terminated;
+                }
+            }
+        }
 
-        let mut arguments = spec.arguments.iter().peekable();
+        // Defaults are parsed once, so that an invalid default is reported here
+        // rather than to everyone who omits the argument.
+        let mut defaults = Vec::with_capacity(spec.arguments.len());
+        for argument in &spec.arguments {
+            defaults.push(match argument {
+                Argument::Parser {
+                    name,
+                    checker,
+                    default: Some(default),
+                    ..
+                } => match checker.parse_default(default) {
+                    Some(value) => Some(Arc::from(value)),
+                    None => return Err(RegisterError::InvalidDefault { name: name.clone() }),
+                },
+                _ => None,
+            });
+        }
 
-        let mut node_key: Option<NodeKey> = None;
+        let bare_name = match (&spec.namespace, &spec.arguments[0]) {
+            (None, _) => None,
+            (Some(_), Argument::Literal { value }) => Some(value.to_string()),
+            (Some(_), Argument::Parser { .. }) => return Err(RegisterError::NamespacedParser),
+        };
+        let arguments: Cow<[Argument<C>]> = match (&spec.namespace, &bare_name) {
+            (Some(namespace), Some(name)) => {
+                let mut arguments = spec.arguments.clone();
+                arguments[0] = Argument::Literal {
+                    value: format!("{}:{}", namespace, name).into(),
+                };
+                Cow::Owned(arguments)
+            }
+            _ => Cow::Borrowed(&spec.arguments),
+        };
 
-        'argument: while let Some(argument) = arguments.peek() {
-            let children = match node_key {
-                Some(key) => &self.nodes[*key].children,
-                None => &self.children,
+        if let Some(name) = &bare_name {
+            if let Some(existing) = self.find_root_literal(name) {
+                let node = &self.nodes[*existing];
+                if node.redirect.is_some() && !node.bare_name {
+                    return Err(RegisterError::AliasConflict);
+                }
+            }
+        }
+
+        // Aliases redirect to the node of the first argument.
+        if !spec.aliases.is_empty() {
+            let first = match arguments.first() {
+                Some(first @ Argument::Literal { .. }) => first,
+                _ => return Err(RegisterError::AliasedParser),
             };
+            let target = self
+                .children
+                .iter()
+                .find(|child_key| self.nodes[***child_key].argument == *first)
+                .copied();
 
-            for child_key in children {
-                let child = &self.nodes[**child_key];
+            for alias in &spec.aliases {
+                if let Some(existing) = self.find_root_literal(alias) {
+                    let node = &self.nodes[*existing];
+                    if node.redirect.is_none() || node.redirect != target || node.bare_name {
+                        return Err(RegisterError::AliasConflict);
+                    }
+                }
+            }
+        }
 
-                if argument == &&child.argument {
-                    arguments.next();
-                    node_key = Some(*child_key);
-                    continue 'argument;
+        // A command without a namespace owns its name, replacing the bare name
+        // of namespaced commands, which is restored when it is unregistered.
+        // Nothing below can fail for a command whose first node is new.
+        if let (None, Some(Argument::Literal { value })) = (&spec.namespace, arguments.first()) {
+            if let Some(existing) = self.find_root_literal(value) {
+                if self.nodes[*existing].bare_name {
+                    self.nodes.remove(*existing);
+                    self.children.retain(|key| *key != existing);
                 }
             }
-            break;
         }
 
-        for argument in arguments {
+        // Walk the nodes which already exist for a prefix of the command.
+        let mut path: Vec<NodeKey> = Vec::with_capacity(arguments.len());
+        for argument in arguments.iter() {
+            let children = match path.last() {
+                Some(key) => &self.nodes[**key].children,
+                None => &self.children,
+            };
+
+            let child_key = match children
+                .iter()
+                .find(|child_key| *argument == self.nodes[***child_key].argument)
+            {
+                Some(child_key) => *child_key,
+                None => break,
+            };
+
+            if self.nodes[*child_key].redirect.is_some() {
+                return Err(RegisterError::AliasConflict);
+            }
+
+            path.push(child_key);
+        }
+
+        // The command may end before each of its trailing optional arguments.
+        let required = spec
+            .arguments
+            .iter()
+            .rposition(|argument| !argument.is_optional())
+            .map_or(1, |i| i + 1);
+
+        let overlaps = path.iter().skip(required - 1).any(|key| {
+            self.nodes[**key].execs.iter().any(|command_index| {
+                !(spec.overload && self.commands[*command_index].spec.overload)
+            })
+        });
+        if overlaps {
+            return Err(RegisterError::OverlappingCommands);
+        }
+
+        for (argument, key) in arguments.iter().zip(&path) {
+            if let (
+                Argument::Parser {
+                    suggestions: Some(suggestions),
+                    ..
+                },
+                Argument::Parser {
+                    suggestions: existing @ None,
+                    ..
+                },
+            ) = (argument, &mut self.nodes[**key].argument)
+            {
+                *existing = Some(*suggestions);
+            }
+        }
+
+        for argument in &arguments[path.len()..] {
             let child = Node::from(argument.clone());
             let child_key = NodeKey(self.nodes.insert(child));
+            self.insert_child(path.last().copied(), child_key);
+            path.push(child_key);
+        }
+
+        let command_index = self.commands.vacant_key();
+        for key in &path {
+            self.nodes[**key].commands.push(command_index);
+        }
+
+        for alias in &spec.aliases {
+            let alias_key = match self.find_root_literal(alias) {
+                Some(alias_key) => alias_key,
+                None => {
+                    let mut node = Node::from(Argument::Literal {
+                        value: alias.clone(),
+                    });
+                    node.redirect = Some(path[0]);
+                    let alias_key = NodeKey(self.nodes.insert(node));
+                    self.insert_child(None, alias_key);
+                    alias_key
+                }
+            };
+            self.nodes[*alias_key].commands.push(command_index);
+        }
+
+        for key in &path[required - 1..] {
+            self.nodes[**key].execs.push(command_index);
+        }
+
+        drop(arguments);
+        let generation = self.generation;
+        self.generation += 1;
+        self.commands.insert(RegisteredCommand {
+            spec,
+            defaults,
+            generation,
+        });
+
+        if let Some(name) = bare_name {
+            self.bare_names.entry(name.clone()).or_insert(None);
+            self.update_bare_name(&name);
+        }
+
+        Ok(CommandHandle {
+            index: command_index,
+            generation,
+        })
+    }
+
+    /// Removes a command registered to this `CommandDispatcher`,
+    /// along with the nodes which no other command uses.
+    ///
+    /// Returns the `CommandSpec` of the command, or `None`
+    /// if it has already been unregistered.
+    pub fn unregister(&mut self, handle: CommandHandle) -> Option<CommandSpec<C>> {
+        let command_index = handle.index;
+        match self.commands.get(command_index) {
+            Some(command) if command.generation == handle.generation => (),
+            _ => return None,
+        }
 
-            if let Some(node_key) = node_key {
-                let node = &mut self.nodes[*node_key];
-                node.children.push(child_key);
-            } else {
-                self.children.push(child_key);
+        let mut unused = Vec::new();
+        for (key, node) in self.nodes.iter_mut() {
+            node.execs.retain(|index| *index != command_index);
+            node.commands.retain(|index| *index != command_index);
+            if node.commands.is_empty() {
+                unused.push(NodeKey(key));
             }
+        }
 
-            node_key = Some(child_key);
+        // The descendants of an unused node are unused as well,
+        // so each unused node only has to be detached from its parent.
+        for key in &unused {
+            self.nodes.remove(**key);
+        }
+        self.children.retain(|key| !unused.contains(key));
+        for (_, node) in self.nodes.iter_mut() {
+            node.children.retain(|key| !unused.contains(key));
         }
 
-        if let Some(key) = node_key {
-            let node = &mut self.nodes[*key];
-            node.execs.push(spec.exec.clone());
-        } else {
-            // Command with zero arguments?
-            return Err(RegisterError::ExecutableRoot);
+        let spec = self.commands.remove(command_index).spec;
+        if let Some(Argument::Literal { value }) = spec.arguments.first() {
+            // The bare name is forgotten with the last namespaced command using it.
+            let namespaced = self.commands.iter().any(|(_, command)| {
+                command.spec.namespace.is_some() && command.spec.arguments[0] == spec.arguments[0]
+            });
+            if spec.namespace.is_some() && !namespaced {
+                self.bare_names.remove(value.as_ref());
+            }
+            if self.bare_names.contains_key(value.as_ref()) {
+                self.update_bare_name(value);
+            }
         }
+        Some(spec)
+    }
 
-        self.commands.push(spec);
+    /// Makes the bare name of namespaced commands, e.g. `kill`, resolve to
+    /// `namespace:kill` rather than to the namespace registered first.
+    ///
+    /// The preference is kept if no command of `namespace` named `name`
+    /// is registered yet, until the last namespaced command named `name`
+    /// is unregistered. A command registered without a namespace
+    /// under `name` takes precedence over any namespace.
+    pub fn prefer_namespace(&mut self, name: &str, namespace: &str) {
+        self.bare_names
+            .insert(name.to_owned(), Some(namespace.to_owned()));
+        self.update_bare_name(name);
+    }
 
-        Ok(())
+    /// Registers every command of a tree built with `literal` and `argument`.
+    ///
+    /// If a command fails to register, the commands of the tree
+    /// registered before it are unregistered again.
+    pub fn register_tree(
+        &mut self,
+        tree: ArgumentBuilder<C>,
+    ) -> Result<Vec<CommandHandle>, RegisterError>
+    where
+        C: 'static,
+    {
+        let mut handles = Vec::new();
+        for spec in tree.build() {
+            match self.register(spec) {
+                Ok(handle) => handles.push(handle),
+                Err(e) => {
+                    for handle in handles {
+                        self.unregister(handle);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(handles)
     }
 
     /// Method-chaining function to register a command.
@@ -116,57 +415,485 @@ where
         self
     }
 
-    /// Dispatches a command. Returns whether a command was executed.
-    pub async fn dispatch<'a, 'b, 'c>(
+    /// Dispatches a command.
+    ///
+    /// Nodes which are only used by commands whose requirement `ctx` does
+    /// not meet are skipped, as if they were not registered.
+    ///
+    /// The siblings of a node are tried literals first, then parsers
+    /// from the highest priority to the lowest.
+    ///
+    /// Returns the result of the first handler that succeeded. If no handler
+    /// succeeded, returns the errors of the handlers that were executed or,
+    /// if none were, the parse failure furthest into the input.
+    pub async fn dispatch<'a>(
         &self,
-        nodes: &'b mut Vec<(&'a str, NodeKey)>,
-        errors: &'c mut Vec<C::Error>,
+        nodes: &mut Vec<(StringReader<'a>, NodeKey, usize)>,
         ctx: &mut C,
         command: &'a str,
-    ) -> Result<C::Ok, &'c Vec<C::Error>> {
+    ) -> Result<C::Ok, DispatchError<C::Error>> {
         nodes.clear();
-        errors.clear();
 
-        for child_key in &self.children {
-            nodes.push((&command, *child_key));
+        let mut errors = Vec::new();
+        let mut failure = None;
+        // Input ranges and parsed values of the nodes leading up to
+        // the current one, indexed by depth.
+        let mut path: Vec<PathEntry> = Vec::new();
+
+        // Children are pushed in reverse so that the first child is tried first.
+        for child_key in self.children.iter().rev() {
+            if self.is_visible(*child_key, ctx) {
+                nodes.push((StringReader::new(command), *child_key, 0));
+            }
         }
 
-        while let Some((mut input, node_key)) = nodes.pop() {
+        while let Some((mut input, node_key, depth)) = nodes.pop() {
             let node = &self.nodes[*node_key];
-            let satisfies = match &node.argument {
-                Argument::Literal { value } => value == input.advance_until(" "),
-                Argument::Parser { checker, .. } => checker.satisfies(ctx, &mut input).await,
+            let start = input.cursor();
+            path.truncate(depth);
+
+            let (satisfies, value) = match &node.argument {
+                Argument::Literal { value } => (value == input.read_until(" "), None),
+                Argument::Parser { checker, .. } => match checker.parse(ctx, &mut input).await {
+                    Some(value) => (true, Some(Arc::from(value))),
+                    None => (false, None),
+                },
             };
 
-            if input.is_empty() && satisfies {
-                for exec in &node.execs {
-                    match exec(ctx, command).await {
+            if !satisfies {
+                let range = token_range(command, start, input.cursor());
+                let error = match &node.argument {
+                    Argument::Literal { .. } if start == 0 => {
+                        DispatchError::UnknownCommand { range }
+                    }
+                    Argument::Literal { .. } => DispatchError::UnknownArgument { range },
+                    Argument::Parser { name, checker, .. } => {
+                        match checker.out_of_range(&command[range.clone()]) {
+                            Some(bounds) => DispatchError::OutOfRange {
+                                name: name.clone(),
+                                bounds,
+                                range,
+                            },
+                            None => DispatchError::ArgumentParse {
+                                name: name.clone(),
+                                expected: checker.expected(),
+                                range,
+                            },
+                        }
+                    }
+                };
+                record_failure(&mut failure, error);
+                continue;
+            }
+
+            path.push((token_range(command, start, input.cursor()), value));
+            let node = self.resolve(node);
+
+            if !input.can_read() {
+                let mut executed = false;
+                for command_index in &node.execs {
+                    if !self.permits(*command_index, ctx) {
+                        continue;
+                    }
+                    executed = true;
+                    let context = self.command_context(*command_index, &path, command);
+                    let exec = &self.commands[*command_index].spec.exec;
+                    match exec.call(ctx, context).await {
                         Ok(ok) => return Ok(ok),
                         Err(err) => errors.push(err),
                     }
                 }
+                if !executed {
+                    record_failure(
+                        &mut failure,
+                        DispatchError::Incomplete {
+                            position: command.len(),
+                        },
+                    );
+                }
                 continue;
             }
 
+            let pushed = nodes.len();
+            for child_key in node.children.iter().rev() {
+                if self.is_visible(*child_key, ctx) {
+                    nodes.push((input, *child_key, depth + 1));
+                }
+            }
+
+            if nodes.len() == pushed {
+                record_failure(
+                    &mut failure,
+                    DispatchError::TrailingInput {
+                        range: input.cursor()..command.len(),
+                    },
+                );
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(DispatchError::Handler(errors));
+        }
+        Err(failure.unwrap_or(DispatchError::UnknownCommand {
+            range: 0..command.len(),
+        }))
+    }
+
+    /// Returns the completions for the token under the cursor.
+    ///
+    /// Only the input before `cursor` is considered. The graph is walked
+    /// the same way as in `dispatch`; every node whose token reaches the cursor
+    /// contributes its candidates: literal nodes their value if it starts with
+    /// the partial token, parser nodes the candidates of their `Suggest` function
+    /// or, if they have none, of their `ArgumentChecker`.
+    ///
+    /// # Panics
+    /// Panics if `cursor` does not lie on a `char` boundary of `input`.
+    pub async fn suggest(&self, ctx: &C, input: &str, cursor: usize) -> Suggestions {
+        let input = &input[..cursor.min(input.len())];
+
+        let start = input.rfind(' ').map(|i| i + 1).unwrap_or(0);
+        let mut suggestions = Suggestions::new(start..input.len());
+
+        let mut nodes: Vec<(StringReader, NodeKey)> = self
+            .children
+            .iter()
+            .filter(|child_key| self.is_visible(**child_key, ctx))
+            .map(|child_key| (StringReader::new(input), *child_key))
+            .collect();
+
+        while let Some((mut reader, node_key)) = nodes.pop() {
+            let node = &self.nodes[*node_key];
+            let remaining = reader.remaining();
+
+            // The token under the cursor, if this node is parsing it. A variadic
+            // argument parses it if it accepts all the tokens before it.
+            let partial = match remaining.rfind(' ') {
+                None => Some(remaining),
+                Some(i) => match &node.argument {
+                    Argument::Parser { checker, .. } if checker.is_variadic() => {
+                        let mut head = StringReader::new(&remaining[..i]);
+                        match checker.parse(ctx, &mut head).await {
+                            Some(_) if !head.can_read() => Some(&remaining[i + 1..]),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                },
+            };
+
+            if let Some(partial) = partial {
+                match &node.argument {
+                    Argument::Literal { value } => {
+                        if value.starts_with(partial) {
+                            suggestions.suggestions.push(value.to_string());
+                        }
+                    }
+                    Argument::Parser {
+                        suggestions: Some(suggest),
+                        ..
+                    } => suggestions.suggestions.extend(suggest(ctx, partial).await),
+                    Argument::Parser { checker, .. } => suggestions
+                        .suggestions
+                        .extend(checker.suggestions(ctx, partial).await),
+                }
+                if !remaining.contains(' ') {
+                    continue;
+                }
+            }
+
+            let satisfies = match &node.argument {
+                Argument::Literal { value } => value == reader.read_until(" "),
+                Argument::Parser { checker, .. } => checker.parse(ctx, &mut reader).await.is_some(),
+            };
+
             if satisfies {
-                for child_key in &node.children {
-                    nodes.push((&mut input, *child_key));
+                for child_key in &self.resolve(node).children {
+                    if self.is_visible(*child_key, ctx) {
+                        nodes.push((reader, *child_key));
+                    }
                 }
             }
         }
-        Err(&*errors)
+
+        suggestions.suggestions.sort();
+        suggestions.suggestions.dedup();
+        suggestions
+    }
+
+    /// Returns the pairs of sibling arguments for which some input satisfies both.
+    ///
+    /// For each pair, the examples of one argument are checked against the
+    /// other. Such inputs are dispatched through both branches, which usually
+    /// means that one command shadows the other.
+    ///
+    /// No context is needed: examples are checked with
+    /// `ArgumentChecker::parse_default`, so arguments whose checker does not
+    /// implement it are only reported when their own examples are ambiguous.
+    pub fn find_ambiguities(&self) -> Vec<Ambiguity> {
+        let mut ambiguities = Vec::new();
+        let mut stack = vec![(String::new(), &self.children)];
+
+        while let Some((path, children)) = stack.pop() {
+            for (i, first_key) in children.iter().enumerate() {
+                for second_key in &children[i + 1..] {
+                    let first = &self.nodes[**first_key].argument;
+                    let second = &self.nodes[**second_key].argument;
+
+                    let mut inputs = Vec::new();
+                    for (argument, other) in &[(first, second), (second, first)] {
+                        for example in argument.examples() {
+                            if accepts(other, &example) {
+                                inputs.push(example.into_owned());
+                            }
+                        }
+                    }
+                    inputs.sort();
+                    inputs.dedup();
+
+                    if !inputs.is_empty() {
+                        ambiguities.push(Ambiguity {
+                            path: path.clone(),
+                            first: first.to_string(),
+                            second: second.to_string(),
+                            inputs,
+                        });
+                    }
+                }
+            }
+
+            for child_key in children {
+                let node = &self.nodes[**child_key];
+                // The children of an alias are checked at its target.
+                if node.redirect.is_some() || node.children.is_empty() {
+                    continue;
+                }
+                let path = if path.is_empty() {
+                    node.argument.to_string()
+                } else {
+                    format!("{} {}", path, node.argument)
+                };
+                stack.push((path, &node.children));
+            }
+        }
+
+        ambiguities
     }
 
     pub fn commands(&self) -> impl Iterator<Item = &CommandSpec<C>> {
-        self.commands.iter()
+        self.commands.iter().map(|(_, command)| &command.spec)
+    }
+
+    /// Points the bare name of namespaced commands at the
+    /// `namespace:name` node of the namespace it resolves to.
+    fn update_bare_name(&mut self, name: &str) {
+        let suffix = format!(":{}", name);
+        let candidates: Vec<(NodeKey, &str)> = self
+            .children
+            .iter()
+            .filter(|child_key| self.nodes[***child_key].redirect.is_none())
+            .filter_map(|child_key| match &self.nodes[**child_key].argument {
+                Argument::Literal { value } => value
+                    .strip_suffix(suffix.as_str())
+                    .map(|namespace| (*child_key, namespace)),
+                Argument::Parser { .. } => None,
+            })
+            .collect();
+
+        let preferred = self.bare_names.get(name).and_then(Option::as_deref);
+        let target = candidates
+            .iter()
+            .find(|(_, namespace)| Some(*namespace) == preferred)
+            .or_else(|| candidates.first())
+            .map(|(target, _)| *target);
+
+        let existing = self.find_root_literal(name);
+        match (existing, target) {
+            // A command without a namespace or an alias owns the name.
+            (Some(existing), _) if !self.nodes[*existing].bare_name => {}
+            (Some(existing), Some(target)) => {
+                let commands = self.nodes[*target].commands.clone();
+                let node = &mut self.nodes[*existing];
+                node.redirect = Some(target);
+                node.commands = commands;
+            }
+            (Some(existing), None) => {
+                self.nodes.remove(*existing);
+                self.children.retain(|key| *key != existing);
+            }
+            (None, Some(target)) => {
+                let mut node = Node::from(Argument::Literal {
+                    value: name.to_owned().into(),
+                });
+                node.redirect = Some(target);
+                node.bare_name = true;
+                node.commands = self.nodes[*target].commands.clone();
+                let key = NodeKey(self.nodes.insert(node));
+                self.insert_child(None, key);
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Adds `child_key` to the children of `parent`, or to the root if `None`.
+    ///
+    /// Children are kept sorted by the order in which they are tried during
+    /// dispatch, as given by the `Ord` implementation of `Argument`. Among
+    /// equal arguments, earlier registered ones come first.
+    fn insert_child(&mut self, parent: Option<NodeKey>, child_key: NodeKey) {
+        let children = match parent {
+            Some(parent) => &self.nodes[*parent].children,
+            None => &self.children,
+        };
+        let argument = &self.nodes[*child_key].argument;
+        let index = children
+            .iter()
+            .position(|key| self.nodes[**key].argument < *argument)
+            .unwrap_or_else(|| children.len());
+
+        match parent {
+            Some(parent) => self.nodes[*parent].children.insert(index, child_key),
+            None => self.children.insert(index, child_key),
+        }
+    }
+
+    /// Creates the context for executing a command, naming the nodes on `path`
+    /// after the command's arguments and adding the default values of the
+    /// optional arguments which were omitted.
+    fn command_context<'a>(
+        &self,
+        command_index: usize,
+        path: &[PathEntry],
+        command: &'a str,
+    ) -> CommandContext<'a> {
+        let RegisteredCommand { spec, defaults, .. } = &self.commands[command_index];
+        let mut matched = Vec::with_capacity(path.len());
+        let mut arguments = ParsedArguments::new();
+
+        for (argument, (range, value)) in spec.arguments.iter().zip(path) {
+            let (name, literal) = match argument {
+                Argument::Literal { value } => (value, true),
+                Argument::Parser { name, .. } => (name, false),
+            };
+            matched.push(MatchedNode {
+                name: name.clone(),
+                literal,
+                range: range.clone(),
+            });
+            if let Some(value) = value {
+                arguments.insert(name.clone(), Arc::clone(value));
+            }
+        }
+
+        for (argument, default) in spec.arguments.iter().zip(defaults).skip(path.len()) {
+            if let (Argument::Parser { name, .. }, Some(default)) = (argument, default) {
+                arguments.insert(name.clone(), Arc::clone(default));
+            }
+        }
+
+        CommandContext::new(command, matched, arguments)
+    }
+
+    /// Returns the root node with the given literal value.
+    fn find_root_literal(&self, value: &str) -> Option<NodeKey> {
+        self.children
+            .iter()
+            .copied()
+            .find(|child_key| match &self.nodes[**child_key].argument {
+                Argument::Literal { value: literal } => literal == value,
+                Argument::Parser { .. } => false,
+            })
+    }
+
+    /// Returns the node which `node` redirects to, or `node` itself.
+    fn resolve<'n>(&'n self, node: &'n Node<C>) -> &'n Node<C> {
+        match node.redirect {
+            Some(target) => &self.nodes[*target],
+            None => node,
+        }
+    }
+
+    /// Returns whether `ctx` meets the requirement of any command using the node.
+    fn is_visible(&self, node_key: NodeKey, ctx: &C) -> bool {
+        self.resolve(&self.nodes[*node_key])
+            .commands
+            .iter()
+            .any(|command_index| self.permits(*command_index, ctx))
+    }
+
+    /// Returns whether `ctx` meets the requirement of the command.
+    fn permits(&self, command_index: usize, ctx: &C) -> bool {
+        match self.commands[command_index].spec.requirement {
+            Some(requirement) => requirement(ctx),
+            None => true,
+        }
+    }
+}
+
+/// Returns whether `argument` accepts the whole of `input`.
+fn accepts<C: Context>(argument: &Argument<C>, input: &str) -> bool {
+    match argument {
+        Argument::Literal { value } => value == input,
+        Argument::Parser { checker, .. } => checker.parse_default(input).is_some(),
     }
 }
 
+/// Returns the range of the token in `command` which was read
+/// from `start` up to `end`, excluding the separator after it.
+fn token_range(command: &str, start: usize, end: usize) -> Range<usize> {
+    let token = command[start..end].trim_end_matches(' ');
+    start..start + token.len()
+}
+
+/// Keeps the failure furthest into the input. At the same position,
+/// a rejected argument is more specific than a literal mismatch.
+fn record_failure<E>(failure: &mut Option<DispatchError<E>>, error: DispatchError<E>) {
+    let replace = match failure {
+        None => true,
+        Some(current) => match error.position().cmp(&current.position()) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Equal => is_argument_error(&error) && !is_argument_error(current),
+            std::cmp::Ordering::Less => false,
+        },
+    };
+    if replace {
+        *failure = Some(error);
+    }
+}
+
+fn is_argument_error<E>(error: &DispatchError<E>) -> bool {
+    matches!(
+        error,
+        DispatchError::ArgumentParse { .. } | DispatchError::OutOfRange { .. }
+    )
+}
+
+/// Command registered to a `CommandDispatcher`.
+struct RegisteredCommand<C: Context> {
+    spec: CommandSpec<C>,
+    /// Default values of the command's arguments, parsed when it was registered.
+    defaults: Vec<Option<Arc<dyn Any + Send + Sync>>>,
+    /// Distinguishes the command from earlier commands in the same slot.
+    generation: u64,
+}
+
+/// Input range and parsed value, if any, of a node during dispatch.
+type PathEntry = (Range<usize>, Option<Arc<dyn Any + Send + Sync>>);
+
 /// Node on the command graph.
 struct Node<C: Context> {
     children: SmallVec<[NodeKey; 4]>,
     argument: Argument<C>,
-    execs: Vec<Exec<C>>,
+    /// Indices of the commands which can be executed at this node.
+    execs: Vec<usize>,
+    /// Indices of the commands whose path contains this node.
+    commands: SmallVec<[usize; 2]>,
+    /// Node whose children and executables are used in place of this
+    /// node's own, used for aliases.
+    redirect: Option<NodeKey>,
+    /// Whether this node is the bare name of namespaced commands,
+    /// which `update_bare_name` maintains.
+    bare_name: bool,
 }
 
 impl<C: Context> From<Argument<C>> for Node<C> {
@@ -175,40 +902,9 @@ impl<C: Context> From<Argument<C>> for Node<C> {
             children: Default::default(),
             argument,
             execs: Vec::new(),
+            commands: SmallVec::new(),
+            redirect: None,
+            bare_name: false,
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    /*use super::*;
-    use bstr::B;
-    use smallvec::smallvec;
-
-    #[test]
-    fn parse_into_arguments() {
-        let test: Vec<(&[u8], SmallVec<[&[u8]; 4]>)> = vec![
-            (
-                B("test 20 \"this is a string: \\\"Hello world\\\"\""),
-                smallvec![B("test"), B("20"), B("this is a string: \"Hello world\"")],
-            ),
-            (
-                B("big inputs cost big programmers with big skills"),
-                smallvec![
-                    B("big"),
-                    B("inputs"),
-                    B("cost"),
-                    B("big"),
-                    B("programmers"),
-                    B("with"),
-                    B("big"),
-                    B("skills"),
-                ],
-            ),
-        ];
-
-        for (input, expected) in test {
-            assert_eq!(CommandDispatcher::parse_into_arguments(input), expected);
-        }
-    }*/
-}
@@ -0,0 +1,144 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::Range;
+
+/// Error returned when dispatching a command fails.
+///
+/// Ranges and positions are byte offsets into the dispatched input.
+#[derive(Debug, PartialEq)]
+pub enum DispatchError<E> {
+    /// No registered command starts with the input.
+    UnknownCommand { range: Range<usize> },
+    /// The input matched a command up to `range`, where none
+    /// of the possible arguments accepted it.
+    UnknownArgument { range: Range<usize> },
+    /// The argument `name` could not be parsed.
+    ArgumentParse {
+        name: Cow<'static, str>,
+        expected: Cow<'static, str>,
+        range: Range<usize>,
+    },
+    /// The argument `name` is a valid value, but outside of the accepted
+    /// bounds, such as "at most 64".
+    OutOfRange {
+        name: Cow<'static, str>,
+        bounds: Cow<'static, str>,
+        range: Range<usize>,
+    },
+    /// The input ended before the command was complete.
+    Incomplete { position: usize },
+    /// A command was matched but more input follows it.
+    TrailingInput { range: Range<usize> },
+    /// The input matched a command, but its handlers failed.
+    Handler(Vec<E>),
+}
+
+impl<E> DispatchError<E> {
+    /// Returns the position in the input at which dispatching failed,
+    /// or `None` if a handler failed.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            DispatchError::UnknownCommand { range }
+            | DispatchError::UnknownArgument { range }
+            | DispatchError::ArgumentParse { range, .. }
+            | DispatchError::OutOfRange { range, .. }
+            | DispatchError::TrailingInput { range } => Some(range.start),
+            DispatchError::Incomplete { position } => Some(*position),
+            DispatchError::Handler(_) => None,
+        }
+    }
+}
+
+/// Renders dispatch errors in the style of Minecraft,
+/// e.g. `expected i32 at position 9: teleport foo<--[HERE]`.
+#[derive(Clone, Debug)]
+pub struct ErrorRenderer {
+    /// Maximum number of characters of input shown before the marker.
+    pub context: usize,
+    /// Prefix shown when the input before the context was cut off.
+    pub ellipsis: Cow<'static, str>,
+    /// Marker placed right after the input which caused the error.
+    pub marker: Cow<'static, str>,
+}
+
+impl Default for ErrorRenderer {
+    fn default() -> Self {
+        Self {
+            context: 10,
+            ellipsis: Cow::Borrowed("..."),
+            marker: Cow::Borrowed("<--[HERE]"),
+        }
+    }
+}
+
+impl ErrorRenderer {
+    /// Creates a renderer with Minecraft's defaults: ten characters
+    /// of context, `...` and `<--[HERE]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders `error`, which occurred while dispatching `input`.
+    ///
+    /// Handler errors have no position in the input,
+    /// so only their message is rendered.
+    pub fn render<E: Display>(&self, input: &str, error: &DispatchError<E>) -> String {
+        let end = match error {
+            DispatchError::UnknownCommand { range }
+            | DispatchError::UnknownArgument { range }
+            | DispatchError::ArgumentParse { range, .. }
+            | DispatchError::OutOfRange { range, .. }
+            | DispatchError::TrailingInput { range } => range.end,
+            DispatchError::Incomplete { position } => *position,
+            DispatchError::Handler(_) => return error.to_string(),
+        };
+
+        let before = &input[..end.min(input.len())];
+        let skipped = before.chars().count().saturating_sub(self.context);
+        let (ellipsis, context) = match before.char_indices().nth(skipped) {
+            _ if skipped == 0 => ("", before),
+            Some((i, _)) => (&*self.ellipsis, &before[i..]),
+            None => (&*self.ellipsis, ""),
+        };
+
+        format!("{}: {}{}{}", error, ellipsis, context, self.marker)
+    }
+}
+
+impl<E: Display> Display for DispatchError<E> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DispatchError::UnknownCommand { range } => {
+                write!(f, "unknown command at position {}", range.start)
+            }
+            DispatchError::UnknownArgument { range } => {
+                write!(f, "incorrect argument at position {}", range.start)
+            }
+            DispatchError::ArgumentParse {
+                expected, range, ..
+            } => write!(f, "expected {} at position {}", expected, range.start),
+            DispatchError::OutOfRange {
+                name,
+                bounds,
+                range,
+            } => write!(f, "{} must be {} at position {}", name, bounds, range.start),
+            DispatchError::Incomplete { position } => {
+                write!(f, "incomplete command at position {}", position)
+            }
+            DispatchError::TrailingInput { range } => {
+                write!(f, "trailing input at position {}", range.start)
+            }
+            DispatchError::Handler(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for DispatchError<E> {}
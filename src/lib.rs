@@ -1,14 +1,37 @@
+mod arguments;
+mod builder;
 mod command;
+mod context;
 mod dispatcher;
+mod error;
 mod parser;
+mod reader;
+mod suggestions;
 
-pub use command::{Argument, Command, CommandSpec};
-pub use dispatcher::CommandDispatcher;
-pub use lieutenant_macros::{command, provider};
-pub use parser::{parsers, ArgumentChecker, ArgumentKind, ArgumentParser, ParserUtil, Provider};
+pub use arguments::ParsedArguments;
+pub use builder::{argument, literal, ArgumentBuilder, ParserBuilder};
+pub use command::{
+    Argument, Command, CommandSpec, Exec, ExecFuture, Executor, Requirement, Suggest,
+};
+pub use context::{CommandContext, MatchedNode};
+pub use dispatcher::{Ambiguity, CommandDispatcher, CommandHandle, RegisterError};
+pub use error::{DispatchError, ErrorRenderer};
+pub use lieutenant_macros::{command, provider, ArgumentKind};
+pub use parser::{parsers, ArgumentChecker, ArgumentKind, CheckerKey, ParsedValue, Provider};
+pub use reader::StringReader;
+pub use suggestions::Suggestions;
 
 /// Denotes a type that may be passed to commands as input.
 pub trait Context: Send + Sync + 'static {
     type Error: std::error::Error + Send + Sync;
     type Ok;
+
+    /// Returns whether this context has the given permission.
+    /// Used by commands declared with `#[command(permission = "...")]`.
+    ///
+    /// The default implementation denies every permission, so that commands
+    /// requiring a permission stay hidden until this method is overridden.
+    fn has_permission(&self, _permission: &str) -> bool {
+        false
+    }
 }
@@ -1,39 +1,105 @@
-use crate::Context;
-use std::any::Any;
+use crate::{Context, StringReader};
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{self, Debug, Formatter};
 use std::future::Future;
+use std::hash::{Hash, Hasher};
 use std::pin::Pin;
 
-pub trait ParserUtil {
-    /// Advances the pointer until the given pattern and returns head and leaving the tail.
-    fn advance_until<'a, 'b>(&'a mut self, pat: &'b str) -> &'a str;
-}
-
-impl ParserUtil for &str {
-    #[inline]
-    fn advance_until<'a, 'b>(&'a mut self, pat: &'b str) -> &'a str {
-        let head = self.split(pat).next().unwrap_or("");
-        *self = &self[(head.len() + pat.len()).min(self.len())..];
-        head
-    }
-}
-
+/// Value obtained from the context rather than from the input.
+///
+/// A parameter of a `#[command]` function which is not named in the usage
+/// is resolved through its type's `Provider` before the command runs.
+/// Implementations are usually generated with `#[provider]`.
 pub trait Provider<C: Context> {
     type Output: Sized;
-    fn provide<'a>(ctx: &C) -> Pin<Box<dyn Future<Output = Result<Self::Output, C::Error>> + Send + Sync + 'a>>;
+    fn provide<'a>(
+        ctx: &'a C,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, C::Error>> + Send + 'a>>;
 }
 
+/// Value produced by an `ArgumentChecker`.
+pub type ParsedValue = Box<dyn Any + Send + Sync>;
+
 pub trait ArgumentChecker<C: Context>: Any + Send + Sync + 'static {
-    fn satisfies<'a, 'b>(
+    /// Parses the argument at the start of `input`, advancing `input` past it.
+    ///
+    /// Returns `None` if the input does not satisfy this checker. The value
+    /// is handed to the command's executor, which downcasts it to the
+    /// `ArgumentKind` this checker belongs to. The context is borrowed for as
+    /// long as the returned future, so that it may be read while parsing.
+    fn parse<'a, 'b>(
+        &self,
+        ctx: &'a C,
+        input: &'a mut StringReader<'b>,
+    ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>>;
+    /// Parses the default value of an optional argument, which is the
+    /// whole of `default`, when the command is registered. Also used by
+    /// `CommandDispatcher::find_ambiguities` to check examples.
+    ///
+    /// Defaults are parsed without a context. The default implementation
+    /// accepts no defaults, so that registering a command with a default
+    /// for this checker fails with `RegisterError::InvalidDefault`.
+    fn parse_default(&self, _default: &str) -> Option<ParsedValue> {
+        None
+    }
+
+    /// Returns whether this checker reads one or more values, which usage
+    /// messages show as `<name>...`.
+    fn is_variadic(&self) -> bool {
+        false
+    }
+
+    /// Returns this checker configured to stop before `literal`, if it reads
+    /// several tokens. Called on registration for every argument which is
+    /// directly followed by a literal, such as `<players>` in
+    /// `teleport <players>... to <target>`.
+    ///
+    /// The default implementation returns `None`, keeping this checker.
+    fn until_literal(&self, _literal: &str) -> Option<Box<dyn ArgumentChecker<C>>> {
+        None
+    }
+
+    /// Returns the identity of this checker, including its configuration.
+    ///
+    /// Parsers whose checkers have equal keys share a node in the command
+    /// graph, so checkers which accept different input must return different keys.
+    fn key(&self) -> CheckerKey;
+
+    /// Returns a description of the accepted input, used in error
+    /// messages such as "expected integer at position 9".
+    fn expected(&self) -> Cow<'static, str> {
+        Cow::Borrowed("argument")
+    }
+
+    /// Returns the bounds which `token` violates, such as "at most 64", if it
+    /// is a valid value outside of the range this checker accepts.
+    ///
+    /// Called after `parse` rejected `token`, to report a
+    /// `DispatchError::OutOfRange` instead of an `ArgumentParse`.
+    fn out_of_range(&self, _token: &str) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    /// Returns completions for the partially typed argument `partial`.
+    ///
+    /// The default implementation suggests nothing.
+    fn suggestions<'a>(
         &self,
-        ctx: &C,
-        input: &'a mut &'b str,
-    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
-    /// Returns whether this `ArgumentChecker` will perform
-    /// the same operation as some other `ArgumentChecker`.
+        _ctx: &'a C,
+        _partial: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + 'a>> {
+        Box::pin(async { Vec::new() })
+    }
+
+    /// Returns inputs which this checker accepts, used by
+    /// `CommandDispatcher::find_ambiguities` to detect overlapping arguments.
     ///
-    /// This is a workaround for the fact that `PartialEq` and `Eq`
-    /// cannot be boxed into trait objects.
-    fn equals(&self, other: &dyn Any) -> bool;
+    /// The default implementation returns no examples.
+    fn examples(&self) -> Vec<Cow<'static, str>> {
+        Vec::new()
+    }
 
     fn default() -> Self
     where
@@ -42,26 +108,84 @@ pub trait ArgumentChecker<C: Context>: Any + Send + Sync + 'static {
     fn box_clone(&self) -> Box<dyn ArgumentChecker<C>>;
 }
 
-pub trait ArgumentParser<C: Context>: Send + Sync + 'static {
-    type Output: Send + Sync;
+/// Identity of an `ArgumentChecker`: its type and its configuration.
+pub struct CheckerKey {
+    checker: TypeId,
+    hash: u64,
+    config: Box<dyn DynKey>,
+}
 
-    fn parse<'a, 'b>(
-        &self,
-        ctx: &mut C,
-        input: &'a mut &'b str,
-    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, C::Error>> + Send + Sync + 'a>>;
-    fn default() -> Self
-    where
-        Self: Sized;
+impl CheckerKey {
+    /// Returns the key of a checker of type `T` without configuration,
+    /// which is equal to the key of every other `T`.
+    pub fn of<T: Any>() -> Self {
+        Self::configured::<T, ()>(())
+    }
+
+    /// Returns the key of a checker of type `T` configured by `config`,
+    /// such as its bounds or the pattern it matches.
+    pub fn configured<T: Any, K: Hash + Eq + Any>(config: K) -> Self {
+        let mut hasher = DefaultHasher::new();
+        config.hash(&mut hasher);
+        Self {
+            checker: TypeId::of::<T>(),
+            hash: hasher.finish(),
+            config: Box::new(config),
+        }
+    }
 }
 
-pub trait ArgumentKind<C: Context>: Sized + Send + Sync {
+impl PartialEq for CheckerKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.checker == other.checker
+            && self.hash == other.hash
+            && self.config.eq_key(other.config.as_any())
+    }
+}
+
+impl Eq for CheckerKey {}
+
+impl Hash for CheckerKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.checker.hash(state);
+        self.hash.hash(state);
+    }
+}
+
+impl Debug for CheckerKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CheckerKey")
+            .field("checker", &self.checker)
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
+/// Object-safe `Eq` for the configuration of a `CheckerKey`.
+trait DynKey: Any {
+    fn as_any(&self) -> &dyn Any;
+
+    fn eq_key(&self, other: &dyn Any) -> bool;
+}
+
+impl<K: Eq + Any> DynKey for K {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_key(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<K>() == Some(self)
+    }
+}
+
+pub trait ArgumentKind<C: Context>: Sized + Send + Sync + 'static {
+    /// Checker whose parsed values are of type `Self`.
     type Checker: ArgumentChecker<C>;
-    type Parser: ArgumentParser<C, Output = Self>;
 }
 
 pub mod parsers {
     use super::*;
+    use std::fmt::Display;
     use std::marker::PhantomData;
     use std::num::*;
     use std::path::PathBuf;
@@ -85,19 +209,35 @@ pub mod parsers {
         C: Context,
         T: FromStr + Clone + Send + Sync + 'static,
     {
-        fn satisfies<'a, 'b>(
+        fn parse<'a, 'b>(
             &self,
-            _ctx: &C,
-            input: &'a mut &'b str,
-        ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+            _ctx: &'a C,
+            input: &'a mut StringReader<'b>,
+        ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>> {
             Box::pin(async move {
-                let head = input.advance_until(" ");
-                T::from_str(head).is_ok()
+                let head = input.read_until(" ");
+                T::from_str(head)
+                    .ok()
+                    .map(|value| Box::new(value) as ParsedValue)
             })
         }
 
-        fn equals(&self, other: &dyn Any) -> bool {
-            other.downcast_ref::<Self>().is_some()
+        fn parse_default(&self, default: &str) -> Option<ParsedValue> {
+            T::from_str(default)
+                .ok()
+                .map(|value| Box::new(value) as ParsedValue)
+        }
+
+        fn key(&self) -> CheckerKey {
+            CheckerKey::of::<Self>()
+        }
+
+        fn expected(&self) -> Cow<'static, str> {
+            Cow::Owned(short_type_name(std::any::type_name::<T>()))
+        }
+
+        fn examples(&self) -> Vec<Cow<'static, str>> {
+            sample_inputs::<T>(SAMPLE_WORDS)
         }
 
         fn default() -> Self
@@ -112,12 +252,175 @@ pub mod parsers {
         }
     }
 
+    /// Checker of a number which must lie within `min` and `max`, set with
+    /// `#[arg(min = 0, max = 64)]` on a `#[command]` parameter.
+    ///
+    /// The parsed value is a `T`, like with `FromStrChecker`.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct BoundedChecker<T> {
+        min: Option<T>,
+        max: Option<T>,
+    }
+
+    impl<T> BoundedChecker<T> {
+        pub fn new(min: Option<T>, max: Option<T>) -> Self {
+            Self { min, max }
+        }
+    }
+
+    impl<T: PartialOrd + Display> BoundedChecker<T> {
+        fn contains(&self, value: &T) -> bool {
+            self.min.iter().all(|min| value >= min) && self.max.iter().all(|max| value <= max)
+        }
+
+        fn bounds(&self) -> Option<String> {
+            match (&self.min, &self.max) {
+                (Some(min), Some(max)) => Some(format!("between {} and {}", min, max)),
+                (Some(min), None) => Some(format!("at least {}", min)),
+                (None, Some(max)) => Some(format!("at most {}", max)),
+                (None, None) => None,
+            }
+        }
+    }
+
+    impl<C, T> ArgumentChecker<C> for BoundedChecker<T>
+    where
+        C: Context,
+        T: FromStr + PartialOrd + Display + Clone + Send + Sync + 'static,
+    {
+        fn parse<'a, 'b>(
+            &self,
+            _ctx: &'a C,
+            input: &'a mut StringReader<'b>,
+        ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>> {
+            let checker = self.clone();
+            Box::pin(async move {
+                let head = input.read_until(" ");
+                T::from_str(head)
+                    .ok()
+                    .filter(|value| checker.contains(value))
+                    .map(|value| Box::new(value) as ParsedValue)
+            })
+        }
+
+        fn parse_default(&self, default: &str) -> Option<ParsedValue> {
+            T::from_str(default)
+                .ok()
+                .filter(|value| self.contains(value))
+                .map(|value| Box::new(value) as ParsedValue)
+        }
+
+        fn key(&self) -> CheckerKey {
+            // Floats are not `Hash`, so the bounds are compared as displayed.
+            let bounds = (
+                self.min.as_ref().map(T::to_string),
+                self.max.as_ref().map(T::to_string),
+            );
+            CheckerKey::configured::<Self, _>(bounds)
+        }
+
+        fn expected(&self) -> Cow<'static, str> {
+            let name = short_type_name(std::any::type_name::<T>());
+            match self.bounds() {
+                Some(bounds) => Cow::Owned(format!("{} {}", name, bounds)),
+                None => Cow::Owned(name),
+            }
+        }
+
+        fn out_of_range(&self, token: &str) -> Option<Cow<'static, str>> {
+            match T::from_str(token) {
+                Ok(value) if !self.contains(&value) => self.bounds().map(Cow::Owned),
+                _ => None,
+            }
+        }
+
+        fn examples(&self) -> Vec<Cow<'static, str>> {
+            let mut examples: Vec<_> = sample_inputs::<T>(SAMPLE_WORDS)
+                .into_iter()
+                .filter(|sample| matches!(T::from_str(sample), Ok(value) if self.contains(&value)))
+                .collect();
+            for bound in self.min.iter().chain(&self.max) {
+                examples.push(Cow::Owned(bound.to_string()));
+            }
+            examples
+        }
+
+        fn default() -> Self
+        where
+            Self: Sized,
+        {
+            Self::new(None, None)
+        }
+
+        fn box_clone(&self) -> Box<dyn ArgumentChecker<C>> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Inputs covering the common `FromStr` types, from which the
+    /// examples of a checker are picked.
+    const SAMPLE_WORDS: &[&str] = &["0", "42", "-1", "1.5", "true", "false", "x", "word"];
+
+    /// Returns the samples which parse as `T`.
+    fn sample_inputs<T: FromStr>(samples: &[&'static str]) -> Vec<Cow<'static, str>> {
+        samples
+            .iter()
+            .filter(|sample| T::from_str(sample).is_ok())
+            .map(|sample| Cow::Borrowed(*sample))
+            .collect()
+    }
+
+    /// Strips the module paths from a type name,
+    /// e.g. `alloc::string::String` becomes `String`.
+    fn short_type_name(name: &str) -> String {
+        let mut short = String::with_capacity(name.len());
+        let mut segment = String::new();
+        for c in name.chars() {
+            if c.is_alphanumeric() || c == '_' || c == ':' {
+                segment.push(c);
+            } else {
+                short.push_str(segment.rsplit("::").next().unwrap_or(""));
+                segment.clear();
+                short.push(c);
+            }
+        }
+        short.push_str(segment.rsplit("::").next().unwrap_or(""));
+        short
+    }
+
+    /// Argument which consumes the rest of the input,
+    /// such as the message in `say <message>`.
+    ///
+    /// A greedy argument must be the last argument of a command.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct Greedy<T = String>(pub T);
+
+    impl<T> Greedy<T> {
+        pub fn into_inner(self) -> T {
+            self.0
+        }
+    }
+
+    impl<T> std::ops::Deref for Greedy<T> {
+        type Target = T;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl<T> std::ops::DerefMut for Greedy<T> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0
+        }
+    }
+
     #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-    pub struct FromStrParser<T> {
+    pub struct GreedyChecker<T> {
         _phantom: PhantomData<T>,
     }
 
-    impl<T> Default for FromStrParser<T> {
+    impl<T> Default for GreedyChecker<T> {
         fn default() -> Self {
             Self {
                 _phantom: PhantomData,
@@ -125,45 +428,375 @@ pub mod parsers {
         }
     }
 
-    impl<C, T> ArgumentParser<C> for FromStrParser<T>
+    impl<C, T> ArgumentChecker<C> for GreedyChecker<T>
     where
         C: Context,
-        C::Error: From<<T as FromStr>::Err>,
-        T: FromStr + Send + Sync + 'static,
-        <T as FromStr>::Err: std::error::Error + Send + Sync,
+        T: FromStr + Clone + Send + Sync + 'static,
     {
-        type Output = T;
+        fn parse<'a, 'b>(
+            &self,
+            _ctx: &'a C,
+            input: &'a mut StringReader<'b>,
+        ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>> {
+            Box::pin(async move {
+                let rest = input.read_remaining();
+                if rest.is_empty() {
+                    return None;
+                }
+                T::from_str(rest)
+                    .ok()
+                    .map(|value| Box::new(Greedy(value)) as ParsedValue)
+            })
+        }
+
+        fn parse_default(&self, default: &str) -> Option<ParsedValue> {
+            if default.is_empty() {
+                return None;
+            }
+            T::from_str(default)
+                .ok()
+                .map(|value| Box::new(Greedy(value)) as ParsedValue)
+        }
+
+        fn key(&self) -> CheckerKey {
+            CheckerKey::of::<Self>()
+        }
+
+        fn expected(&self) -> Cow<'static, str> {
+            Cow::Owned(short_type_name(std::any::type_name::<T>()))
+        }
+
+        fn examples(&self) -> Vec<Cow<'static, str>> {
+            let mut examples = sample_inputs::<T>(SAMPLE_WORDS);
+            examples.extend(sample_inputs::<T>(&["hello world"]));
+            examples
+        }
 
+        fn default() -> Self
+        where
+            Self: Sized,
+        {
+            <Self as Default>::default()
+        }
+
+        fn box_clone(&self) -> Box<dyn ArgumentChecker<C>> {
+            Box::new(self.clone())
+        }
+    }
+
+    impl<C, T> ArgumentKind<C> for Greedy<T>
+    where
+        C: Context,
+        T: FromStr + Clone + Send + Sync + 'static,
+    {
+        type Checker = GreedyChecker<T>;
+    }
+
+    /// String argument which is either a single word or enclosed
+    /// in `"` or `'`, such as `"hello world"` or `'it\'s'`.
+    ///
+    /// Inside quotes, the quote character and `\` are escaped with a backslash.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct QuotedString(pub String);
+
+    impl QuotedString {
+        pub fn into_inner(self) -> String {
+            self.0
+        }
+    }
+
+    impl std::ops::Deref for QuotedString {
+        type Target = String;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl std::ops::DerefMut for QuotedString {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct QuotedStringChecker;
+
+    impl<C: Context> ArgumentChecker<C> for QuotedStringChecker {
         fn parse<'a, 'b>(
             &self,
-            _ctx: &mut C,
-            input: &'a mut &'b str,
-        ) -> Pin<Box<dyn Future<Output = Result<Self::Output, C::Error>> + Send + Sync + 'a>>
+            _ctx: &'a C,
+            input: &'a mut StringReader<'b>,
+        ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>> {
+            Box::pin(async move {
+                input
+                    .read_quoted()
+                    .map(|value| Box::new(QuotedString(value)) as ParsedValue)
+            })
+        }
+
+        fn parse_default(&self, default: &str) -> Option<ParsedValue> {
+            let mut reader = StringReader::new(default);
+            let value = reader.read_quoted()?;
+            if reader.can_read() {
+                return None;
+            }
+            Some(Box::new(QuotedString(value)))
+        }
+
+        fn key(&self) -> CheckerKey {
+            CheckerKey::of::<Self>()
+        }
+
+        fn expected(&self) -> Cow<'static, str> {
+            Cow::Borrowed("quoted string")
+        }
+
+        fn examples(&self) -> Vec<Cow<'static, str>> {
+            vec!["word".into(), "\"quoted phrase\"".into()]
+        }
+
+        fn default() -> Self
+        where
+            Self: Sized,
         {
+            QuotedStringChecker
+        }
+
+        fn box_clone(&self) -> Box<dyn ArgumentChecker<C>> {
+            Box::new(*self)
+        }
+    }
+
+    impl<C: Context> ArgumentKind<C> for QuotedString {
+        type Checker = QuotedStringChecker;
+    }
+
+    /// Checker of one or more space-separated values of the kind `T`, used for
+    /// `Vec<T>` arguments such as `<players>...` in `kick <players>...`.
+    ///
+    /// Values are read until the input ends, the `terminator` follows or a token
+    /// is not a valid `T`. Registering a command sets the terminator to the
+    /// literal which follows the argument, if any.
+    #[derive(Debug)]
+    pub struct VecChecker<T> {
+        terminator: Option<Cow<'static, str>>,
+        _phantom: PhantomData<fn() -> T>,
+    }
+
+    impl<T> Clone for VecChecker<T> {
+        fn clone(&self) -> Self {
+            Self::until(self.terminator.clone())
+        }
+    }
+
+    impl<T> VecChecker<T> {
+        pub fn until(terminator: Option<Cow<'static, str>>) -> Self {
+            Self {
+                terminator,
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<T> Default for VecChecker<T> {
+        fn default() -> Self {
+            Self::until(None)
+        }
+    }
+
+    impl<C, T> ArgumentChecker<C> for VecChecker<T>
+    where
+        C: Context,
+        T: ArgumentKind<C>,
+    {
+        fn parse<'a, 'b>(
+            &self,
+            ctx: &'a C,
+            input: &'a mut StringReader<'b>,
+        ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>> {
+            let terminator = self.terminator.clone();
             Box::pin(async move {
-                let head = input.advance_until(" ");
-                Ok(T::from_str(head)?)
+                let checker = <T::Checker as ArgumentChecker<C>>::default();
+                let mut values = Vec::new();
+                while input.can_read() {
+                    let token = input.remaining().split(' ').next().unwrap_or("");
+                    if terminator.as_deref() == Some(token) {
+                        break;
+                    }
+
+                    let mut reader = *input;
+                    let value = match checker.parse(ctx, &mut reader).await {
+                        Some(value) => value,
+                        None => break,
+                    };
+                    match value.downcast::<T>() {
+                        Ok(value) => values.push(*value),
+                        Err(_) => return None,
+                    }
+                    input.restore(reader);
+                }
+
+                if values.is_empty() {
+                    // Like other checkers, leave the rejected token read.
+                    input.read_until(" ");
+                    None
+                } else {
+                    Some(Box::new(values) as ParsedValue)
+                }
             })
         }
 
+        fn parse_default(&self, default: &str) -> Option<ParsedValue> {
+            let checker = <T::Checker as ArgumentChecker<C>>::default();
+            let values = default
+                .split(' ')
+                .map(|token| checker.parse_default(token)?.downcast::<T>().ok())
+                .map(|value| value.map(|value| *value))
+                .collect::<Option<Vec<T>>>()?;
+            Some(Box::new(values))
+        }
+
+        fn is_variadic(&self) -> bool {
+            true
+        }
+
+        fn until_literal(&self, literal: &str) -> Option<Box<dyn ArgumentChecker<C>>> {
+            Some(Box::new(Self::until(Some(literal.to_owned().into()))))
+        }
+
+        fn key(&self) -> CheckerKey {
+            CheckerKey::configured::<Self, _>(self.terminator.clone())
+        }
+
+        fn expected(&self) -> Cow<'static, str> {
+            let element = <T::Checker as ArgumentChecker<C>>::default();
+            Cow::Owned(format!("one or more {}", element.expected()))
+        }
+
+        fn suggestions<'a>(
+            &self,
+            ctx: &'a C,
+            partial: &'a str,
+        ) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + 'a>> {
+            Box::pin(async move {
+                let element = <T::Checker as ArgumentChecker<C>>::default();
+                element.suggestions(ctx, partial).await
+            })
+        }
+
+        fn examples(&self) -> Vec<Cow<'static, str>> {
+            <T::Checker as ArgumentChecker<C>>::default().examples()
+        }
+
+        fn default() -> Self
+        where
+            Self: Sized,
+        {
+            <Self as Default>::default()
+        }
+
+        fn box_clone(&self) -> Box<dyn ArgumentChecker<C>> {
+            Box::new(self.clone())
+        }
+    }
+
+    impl<C, T> ArgumentKind<C> for Vec<T>
+    where
+        C: Context,
+        T: ArgumentKind<C>,
+    {
+        type Checker = VecChecker<T>;
+    }
+
+    /// Enum argument whose variants are matched by name.
+    ///
+    /// Usually implemented with `#[derive(ArgumentKind)]`, which names each
+    /// variant in snake case unless it sets `#[arg(rename = "...")]`, and
+    /// accepts the names given with `#[arg(aliases(...))]` as well.
+    pub trait EnumArgument: Clone + Send + Sync + 'static {
+        /// Names of the variants, in declaration order, without their aliases.
+        const NAMES: &'static [&'static str];
+
+        /// Returns the variant named or aliased `name`.
+        fn from_name(name: &str) -> Option<Self>;
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct EnumChecker<T> {
+        _phantom: PhantomData<T>,
+    }
+
+    impl<T> Default for EnumChecker<T> {
+        fn default() -> Self {
+            Self {
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<C, T> ArgumentChecker<C> for EnumChecker<T>
+    where
+        C: Context,
+        T: EnumArgument,
+    {
+        fn parse<'a, 'b>(
+            &self,
+            _ctx: &'a C,
+            input: &'a mut StringReader<'b>,
+        ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>> {
+            Box::pin(async move {
+                T::from_name(input.read_until(" ")).map(|value| Box::new(value) as ParsedValue)
+            })
+        }
+
+        fn parse_default(&self, default: &str) -> Option<ParsedValue> {
+            T::from_name(default).map(|value| Box::new(value) as ParsedValue)
+        }
+
+        fn key(&self) -> CheckerKey {
+            CheckerKey::of::<Self>()
+        }
+
+        fn expected(&self) -> Cow<'static, str> {
+            Cow::Owned(format!("one of {}", T::NAMES.join(", ")))
+        }
+
+        fn suggestions<'a>(
+            &self,
+            _ctx: &'a C,
+            partial: &'a str,
+        ) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + 'a>> {
+            Box::pin(async move {
+                T::NAMES
+                    .iter()
+                    .filter(|name| name.starts_with(partial))
+                    .map(|name| (*name).to_owned())
+                    .collect()
+            })
+        }
+
+        fn examples(&self) -> Vec<Cow<'static, str>> {
+            T::NAMES.iter().map(|name| Cow::Borrowed(*name)).collect()
+        }
+
         fn default() -> Self
         where
             Self: Sized,
         {
             <Self as Default>::default()
         }
+
+        fn box_clone(&self) -> Box<dyn ArgumentChecker<C>> {
+            Box::new(self.clone())
+        }
     }
 
     macro_rules! from_str_argument_kind {
         ($($ty:ty,)*) => {
             $(
-                impl <C> ArgumentKind<C> for $ty
-                where
-                    C: Context,
-                    C::Error: From<<$ty as FromStr>::Err>,
-                {
+                impl <C: Context> ArgumentKind<C> for $ty {
                     type Checker = FromStrChecker<Self>;
-                    type Parser = FromStrParser<Self>;
                 }
             )*
         }
@@ -0,0 +1,219 @@
+/// Cursor over the input of a command.
+///
+/// The cursor is a byte offset into the original input, so positions
+/// reported by parsers refer to the whole command. A `StringReader` is
+/// `Copy`; a snapshot is taken by copying it and restored with [`restore`].
+///
+/// [`restore`]: StringReader::restore
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StringReader<'a> {
+    input: &'a str,
+    cursor: usize,
+}
+
+impl<'a> StringReader<'a> {
+    /// Creates a reader at the start of `input`.
+    pub fn new(input: &'a str) -> Self {
+        Self { input, cursor: 0 }
+    }
+
+    /// Returns the whole input, including the part already read.
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    /// Returns the byte offset of the cursor into the input.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor to the given byte offset.
+    ///
+    /// # Panics
+    /// Panics if `cursor` is out of bounds or not on a `char` boundary.
+    pub fn set_cursor(&mut self, cursor: usize) {
+        assert!(self.input.is_char_boundary(cursor));
+        self.cursor = cursor;
+    }
+
+    /// Returns the input after the cursor.
+    pub fn remaining(&self) -> &'a str {
+        &self.input[self.cursor..]
+    }
+
+    /// Returns whether there is input left after the cursor.
+    pub fn can_read(&self) -> bool {
+        self.cursor < self.input.len()
+    }
+
+    /// Returns the next character without advancing.
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    /// Reads the next character.
+    pub fn read(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.cursor += c.len_utf8();
+        Some(c)
+    }
+
+    /// Skips the next character, if any.
+    pub fn skip(&mut self) {
+        self.read();
+    }
+
+    /// Skips the next character if it is `expected`. Returns whether it was.
+    pub fn expect(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.skip();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skips all whitespace after the cursor.
+    pub fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.skip();
+        }
+    }
+
+    /// Reads until the given pattern and returns the input before it.
+    /// The cursor is placed after the pattern, or at the end of the input.
+    pub fn read_until(&mut self, pat: &str) -> &'a str {
+        let remaining = self.remaining();
+        let head = remaining.split(pat).next().unwrap_or("");
+        self.cursor += (head.len() + pat.len()).min(remaining.len());
+        head
+    }
+
+    /// Reads all of the remaining input.
+    pub fn read_remaining(&mut self) -> &'a str {
+        let remaining = self.remaining();
+        self.cursor = self.input.len();
+        remaining
+    }
+
+    /// Reads a word or a string enclosed in `"` or `'` and returns it
+    /// with backslash escapes resolved. A single space after it is skipped.
+    ///
+    /// Returns `None` and reads the rest of the input if the quote is not
+    /// closed, an unknown escape is used or the closing quote is not
+    /// followed by a space or the end of input.
+    pub fn read_quoted(&mut self) -> Option<String> {
+        let quote = match self.peek() {
+            Some(quote @ '"') | Some(quote @ '\'') => quote,
+            _ => return Some(self.read_until(" ").to_owned()),
+        };
+        self.skip();
+
+        let mut string = String::new();
+        let mut escaped = false;
+        while let Some(c) = self.read() {
+            if escaped {
+                if c != quote && c != '\\' {
+                    break;
+                }
+                string.push(c);
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                if self.can_read() && !self.expect(' ') {
+                    break;
+                }
+                return Some(string);
+            } else {
+                string.push(c);
+            }
+        }
+
+        self.read_remaining();
+        None
+    }
+
+    /// Restores a snapshot of this reader taken by copying it.
+    ///
+    /// # Panics
+    /// Panics in debug builds if the snapshot was taken of another input.
+    pub fn restore(&mut self, snapshot: StringReader<'a>) {
+        debug_assert!(std::ptr::eq(self.input, snapshot.input));
+        self.cursor = snapshot.cursor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_until() {
+        let mut reader = StringReader::new("give  stone 64");
+        assert_eq!(reader.read_until(" "), "give");
+        assert_eq!(reader.cursor(), 5);
+        assert_eq!(reader.read_until(" "), "");
+        assert_eq!(reader.read_until(" "), "stone");
+        assert_eq!(reader.read_until(" "), "64");
+        assert_eq!(reader.cursor(), 14);
+        assert!(!reader.can_read());
+    }
+
+    #[test]
+    fn peek_expect_and_whitespace() {
+        let mut reader = StringReader::new("a   b");
+        assert_eq!(reader.peek(), Some('a'));
+        assert!(!reader.expect('b'));
+        assert!(reader.expect('a'));
+        reader.skip_whitespace();
+        assert_eq!(reader.cursor(), 4);
+        assert_eq!(reader.remaining(), "b");
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let mut reader = StringReader::new("tp 1 2");
+        reader.read_until(" ");
+        let snapshot = reader;
+        reader.read_remaining();
+        assert!(!reader.can_read());
+        reader.restore(snapshot);
+        assert_eq!(reader.remaining(), "1 2");
+    }
+
+    #[test]
+    fn read_quoted() {
+        let tests: Vec<(&str, Vec<Option<&str>>)> = vec![
+            (
+                "test 20 \"this is a string: \\\"Hello world\\\"\"",
+                vec![
+                    Some("test"),
+                    Some("20"),
+                    Some("this is a string: \"Hello world\""),
+                ],
+            ),
+            (
+                "'it\\'s' 'back\\\\slash' \"\"",
+                vec![Some("it's"), Some("back\\slash"), Some("")],
+            ),
+            (
+                "big inputs cost",
+                vec![Some("big"), Some("inputs"), Some("cost")],
+            ),
+            ("\"unterminated", vec![None]),
+            ("\"bad\\escape\"", vec![None]),
+            ("\"no\"space", vec![None]),
+        ];
+
+        for (input, expected) in tests {
+            let mut reader = StringReader::new(input);
+            let mut arguments = vec![];
+            while reader.can_read() {
+                arguments.push(reader.read_quoted());
+            }
+            let expected: Vec<_> = expected.into_iter().map(|s| s.map(String::from)).collect();
+            assert_eq!(arguments, expected, "{}", input);
+        }
+    }
+}
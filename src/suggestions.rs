@@ -0,0 +1,26 @@
+use std::ops::Range;
+
+/// Completion candidates for the token under the cursor,
+/// as returned by [`CommandDispatcher::suggest`](crate::CommandDispatcher::suggest).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Suggestions {
+    /// Byte range of the input which is replaced when a suggestion is accepted.
+    pub range: Range<usize>,
+    /// Candidate completions, sorted and without duplicates.
+    pub suggestions: Vec<String>,
+}
+
+impl Suggestions {
+    /// Creates an empty set of suggestions replacing the given range.
+    pub fn new(range: Range<usize>) -> Self {
+        Self {
+            range,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Returns whether there are no candidates.
+    pub fn is_empty(&self) -> bool {
+        self.suggestions.is_empty()
+    }
+}
@@ -0,0 +1,88 @@
+use lieutenant::{command, CommandDispatcher, Context, RegisterError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    position: Option<(i32, i32)>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "teleport <x> <y>", aliases("tp", "tele"))]
+async fn teleport(state: &mut State, x: i32, y: i32) -> Result<(), Error> {
+    state.position = Some((x, y));
+    Ok(())
+}
+
+#[command(usage = "teleport spawn", aliases = "tp")]
+async fn teleport_spawn(state: &mut State) -> Result<(), Error> {
+    state.position = Some((0, 0));
+    Ok(())
+}
+
+#[command(usage = "tp <x>")]
+async fn tp(state: &mut State, x: i32) -> Result<(), Error> {
+    state.position = Some((x, x));
+    Ok(())
+}
+
+#[command(usage = "<x>", aliases = "x")]
+async fn parser_first(_state: &mut State, _x: i32) -> Result<(), Error> {
+    Ok(())
+}
+
+#[test]
+fn aliases_redirect_to_command() {
+    let dispatcher = CommandDispatcher::default()
+        .with(teleport)
+        .with(teleport_spawn);
+    let mut nodes = Vec::new();
+
+    let mut state = State::default();
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "tp 1 2")).is_ok());
+    assert_eq!(state.position, Some((1, 2)));
+
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "tele 3 4")).is_ok());
+    assert_eq!(state.position, Some((3, 4)));
+
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "tp spawn")).is_ok());
+    assert_eq!(state.position, Some((0, 0)));
+
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "tp")).is_err());
+
+    assert_eq!(dispatcher.commands().count(), 2);
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state, "t", 1));
+    assert_eq!(suggestions.suggestions, vec!["tele", "teleport", "tp"]);
+    let suggestions = smol::block_on(dispatcher.suggest(&state, "tp s", 4));
+    assert_eq!(suggestions.suggestions, vec!["spawn"]);
+}
+
+#[test]
+fn alias_conflicts() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(tp).unwrap();
+    assert!(matches!(
+        dispatcher.register(teleport),
+        Err(RegisterError::AliasConflict)
+    ));
+
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(teleport).unwrap();
+    assert!(matches!(
+        dispatcher.register(tp),
+        Err(RegisterError::AliasConflict)
+    ));
+
+    let mut dispatcher = CommandDispatcher::default();
+    assert!(matches!(
+        dispatcher.register(parser_first),
+        Err(RegisterError::AliasedParser)
+    ));
+}
@@ -0,0 +1,82 @@
+use lieutenant::{command, Ambiguity, CommandDispatcher, Context};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+struct State;
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "teleport <x> <y>")]
+async fn teleport(_state: &mut State, _x: i32, _y: i32) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "teleport <player>")]
+async fn teleport_to_player(_state: &mut State, _player: String) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "teleport spawn")]
+async fn teleport_spawn(_state: &mut State) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "give <item> [count=1]")]
+async fn give(_state: &mut State, _item: String, _count: u32) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "time set <ticks>")]
+async fn time_set(_state: &mut State, _ticks: u32) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "time <ticks>")]
+async fn time(_state: &mut State, _ticks: u32) -> Result<(), Error> {
+    Ok(())
+}
+
+#[test]
+fn sibling_arguments_are_reported() {
+    let dispatcher = CommandDispatcher::default()
+        .with(teleport)
+        .with(teleport_to_player)
+        .with(teleport_spawn)
+        .with(give);
+
+    let mut ambiguities = dispatcher.find_ambiguities();
+    ambiguities.sort_by(|a, b| (&a.first, &a.second).cmp(&(&b.first, &b.second)));
+
+    assert_eq!(
+        ambiguities,
+        vec![
+            Ambiguity {
+                path: "teleport".into(),
+                first: "<x>".into(),
+                second: "<player>".into(),
+                inputs: vec!["-1".into(), "0".into(), "42".into()],
+            },
+            Ambiguity {
+                path: "teleport".into(),
+                first: "spawn".into(),
+                second: "<player>".into(),
+                inputs: vec!["spawn".into()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn disjoint_arguments_are_not_reported() {
+    let dispatcher = CommandDispatcher::default()
+        .with(time_set)
+        .with(time)
+        .with(give);
+
+    assert!(dispatcher.find_ambiguities().is_empty());
+}
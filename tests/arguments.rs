@@ -0,0 +1,209 @@
+use lieutenant::parsers::{Greedy, QuotedString};
+use lieutenant::{command, CommandDispatcher, Context, RegisterError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Debug, Default, PartialEq)]
+struct State {
+    item: String,
+    count: Option<u32>,
+    slot: Option<u32>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[test]
+fn optional_parameters() {
+    #[command(usage = "give <item> [count] [slot]")]
+    async fn give(
+        state: &mut State,
+        item: String,
+        count: Option<u32>,
+        slot: Option<u32>,
+    ) -> Result<(), Error> {
+        state.item = item;
+        state.count = count;
+        state.slot = slot;
+        Ok(())
+    }
+
+    let dispatcher = CommandDispatcher::default().with(give);
+    let mut nodes = Vec::new();
+
+    let mut state = State::default();
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give stone")).is_ok());
+    assert_eq!(state.item, "stone");
+    assert_eq!(state.count, None);
+    assert_eq!(state.slot, None);
+
+    let mut state = State::default();
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give stone 5")).is_ok());
+    assert_eq!(state.count, Some(5));
+    assert_eq!(state.slot, None);
+
+    let mut state = State::default();
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give stone 5 2")).is_ok());
+    assert_eq!(state.count, Some(5));
+    assert_eq!(state.slot, Some(2));
+
+    let mut state = State::default();
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give")).is_err());
+    assert!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give stone five")).is_err()
+    );
+}
+
+#[test]
+fn default_values() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Give {
+        item: String,
+        count: u32,
+    }
+
+    impl Context for Give {
+        type Error = Error;
+        type Ok = ();
+    }
+
+    #[command(usage = "give <item> [count=1]")]
+    async fn give(state: &mut Give, item: String, count: u32) -> Result<(), Error> {
+        state.item = item;
+        state.count = count;
+        Ok(())
+    }
+
+    let dispatcher = CommandDispatcher::default().with(give);
+    let mut nodes = Vec::new();
+
+    let mut state = Give::default();
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give stone")).is_ok());
+    assert_eq!(state.count, 1);
+
+    let mut state = Give::default();
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give stone 64")).is_ok());
+    assert_eq!(state.count, 64);
+
+    let usages: Vec<_> = dispatcher.commands().map(|spec| spec.usage()).collect();
+    assert_eq!(usages, vec!["give <item> [count=1]"]);
+}
+
+#[test]
+fn greedy_arguments() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Chat {
+        to: Option<String>,
+        message: String,
+    }
+
+    impl Context for Chat {
+        type Error = Error;
+        type Ok = ();
+    }
+
+    #[command(usage = "say <message>")]
+    async fn say(state: &mut Chat, message: Greedy<String>) -> Result<(), Error> {
+        state.message = message.into_inner();
+        Ok(())
+    }
+
+    #[command(usage = "msg <player> <text>")]
+    async fn msg(state: &mut Chat, player: String, text: Greedy) -> Result<(), Error> {
+        state.to = Some(player);
+        state.message = text.0;
+        Ok(())
+    }
+
+    let dispatcher = CommandDispatcher::default().with(say).with(msg);
+    let mut nodes = Vec::new();
+
+    let mut state = Chat::default();
+    assert!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "say hello to everyone"))
+            .is_ok()
+    );
+    assert_eq!(state.message, "hello to everyone");
+
+    let mut state = Chat::default();
+    assert!(smol::block_on(dispatcher.dispatch(
+        &mut nodes,
+        &mut state,
+        "msg alice see you  later"
+    ))
+    .is_ok());
+    assert_eq!(state.to.as_deref(), Some("alice"));
+    assert_eq!(state.message, "see you  later");
+
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "say")).is_err());
+}
+
+#[test]
+fn quoted_arguments() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Sign {
+        lines: Vec<String>,
+    }
+
+    impl Context for Sign {
+        type Error = Error;
+        type Ok = ();
+    }
+
+    #[command(usage = "sign <first> <second> done")]
+    async fn sign(
+        state: &mut Sign,
+        first: QuotedString,
+        second: QuotedString,
+    ) -> Result<(), Error> {
+        state.lines = vec![first.into_inner(), second.into_inner()];
+        Ok(())
+    }
+
+    let dispatcher = CommandDispatcher::default().with(sign);
+    let mut nodes = Vec::new();
+
+    let mut state = Sign::default();
+    assert!(smol::block_on(dispatcher.dispatch(
+        &mut nodes,
+        &mut state,
+        r#"sign "hello world" 'it\'s' done"#
+    ))
+    .is_ok());
+    assert_eq!(state.lines, vec!["hello world", "it's"]);
+
+    let mut state = Sign::default();
+    assert!(smol::block_on(dispatcher.dispatch(
+        &mut nodes,
+        &mut state,
+        "sign plain 'two words' done"
+    ))
+    .is_ok());
+    assert_eq!(state.lines, vec!["plain", "two words"]);
+
+    assert!(smol::block_on(dispatcher.dispatch(
+        &mut nodes,
+        &mut state,
+        r#"sign "unterminated done"#
+    ))
+    .is_err());
+}
+
+#[test]
+fn invalid_default_values_are_rejected_on_registration() {
+    #[command(usage = "say [message=\"oops]")]
+    async fn say(_state: &mut State, _message: QuotedString) -> Result<(), Error> {
+        Ok(())
+    }
+
+    let mut dispatcher = CommandDispatcher::default();
+    assert!(matches!(
+        dispatcher.register(say),
+        Err(RegisterError::InvalidDefault { name }) if name == "message"
+    ));
+    assert_eq!(dispatcher.commands().count(), 0);
+}
@@ -1,29 +1,12 @@
-use lieutenant::{command, provider, CommandDispatcher, Context};
+use lieutenant::{command, provider, CommandDispatcher, Context, DispatchError};
 use thiserror::Error;
-use std::num;
 
 #[derive(Debug, Error, PartialEq)]
 enum Error {
     #[error("{0}")]
     Custom(String),
-    #[error("failed to parse int")]
-    ParsingInt,
 }
 
-impl From<num::ParseIntError> for Error
-{
-    fn from(_: num::ParseIntError) -> Self {
-        Error::ParsingInt
-    }
-}
-
-impl From<std::convert::Infallible> for Error {
-    fn from(_: std::convert::Infallible) -> Self {
-        panic!()
-    }
-}
-
-
 #[test]
 fn basic_command() {
     #[derive(Debug, PartialEq, Eq)]
@@ -43,11 +26,10 @@ fn basic_command() {
     let dispatcher = CommandDispatcher::default().with(test);
 
     let mut nodes = Vec::new();
-    let mut errors = Vec::new();
 
     let mut x = State(0);
     assert!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut x, "test 27")).is_ok()
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut x, "test 27")).is_ok()
     );
     assert_eq!(x, State(27));
 }
@@ -64,13 +46,13 @@ fn basic_command_with_provider() {
 
     struct Value;
 
-    // #[provider]
-    async fn provde_value(ctx: &State) -> Result<Value, Error> {
+    #[provider]
+    async fn provde_value(_ctx: &State) -> Result<Value, Error> {
         Ok(Value)
     }
 
     #[command(usage = "test <x>")]
-    async fn test(ctx: &mut State, x: i32) -> Result<(), Error> {
+    async fn test(ctx: &mut State, x: i32, _value: Value) -> Result<(), Error> {
         *ctx = State(x);
         Ok(())
     };
@@ -78,11 +60,10 @@ fn basic_command_with_provider() {
     let dispatcher = CommandDispatcher::default().with(test);
 
     let mut nodes = Vec::new();
-    let mut errors = Vec::new();
 
     let mut x = State(0);
     assert!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut x, "test 27")).is_ok()
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut x, "test 27")).is_ok()
     );
     assert_eq!(x, State(27));
 }
@@ -119,16 +100,14 @@ fn basic_command_parralel() {
     let dispatcher = CommandDispatcher::default().with(test);
 
     let mut nodes_a = Vec::new();
-    let mut errors_a = Vec::new();
 
     let mut nodes_b = Vec::new();
-    let mut errors_b = Vec::new();
 
     let mut a = State(0);
     let mut b = State(0);
 
-    let call_a = dispatcher.dispatch(&mut nodes_a, &mut errors_a, &mut a, "test 27");
-    let call_b = dispatcher.dispatch(&mut nodes_b, &mut errors_b, &mut b, "test 27");
+    let call_a = dispatcher.dispatch(&mut nodes_a, &mut a, "test 27");
+    let call_b = dispatcher.dispatch(&mut nodes_b, &mut b, "test 27");
 
     let now = Instant::now();
 
@@ -159,15 +138,16 @@ fn error_handling() {
     let dispatcher = CommandDispatcher::default().with(test);
 
     let mut nodes = Vec::new();
-    let mut errors = Vec::new();
 
     assert_eq!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut State, "test 0")),
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut State, "test 0")),
         Ok(())
     );
     assert_eq!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut State, "test 5")),
-        Err(&vec![Error::Custom("Not zero".into())])
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut State, "test 5")),
+        Err(DispatchError::Handler(vec![Error::Custom(
+            "Not zero".into()
+        )]))
     );
 }
 
@@ -194,7 +174,6 @@ fn multiple_args() {
     dispatcher.register(test14).unwrap();
 
     let mut nodes = Vec::new();
-    let mut errors = Vec::new();
 
     let mut state = State {
         x: 690_854,
@@ -202,7 +181,6 @@ fn multiple_args() {
     };
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "test14 66 string extra_literal"
     ))
@@ -239,7 +217,6 @@ fn multiple_commands() {
     let dispatcher = CommandDispatcher::default().with(cmd1).with(cmd2);
 
     let mut nodes = Vec::new();
-    let mut errors = Vec::new();
 
     let mut state = State {
         x: 32,
@@ -247,13 +224,12 @@ fn multiple_commands() {
     };
 
     assert!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut state, "cmd1 10"))
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "cmd1 10"))
             .is_err()
     ); // misssing extra_lit
 
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "cmd1 10 extra_lit"
     ))
@@ -262,7 +238,6 @@ fn multiple_commands() {
 
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "invalid command 22"
     ))
@@ -270,7 +245,6 @@ fn multiple_commands() {
 
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "cmd2 new_string"
     ))
@@ -314,7 +288,6 @@ fn command_macro() {
         .with(foo_a_player)
         .with(foo_a_player_then_bar_an_x);
 
-    let mut errors = Vec::new();
     let mut nodes = Vec::new();
 
     let mut state = State {
@@ -323,20 +296,18 @@ fn command_macro() {
     };
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "false command"
     ))
     .is_err());
 
     assert!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut state, "test 25")).is_ok()
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "test 25")).is_ok()
     );
     assert_eq!(state.x, 25);
 
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "foo twenty-six"
     ))
@@ -344,29 +315,27 @@ fn command_macro() {
     assert_eq!(state.player.as_str(), "twenty-six");
 
     assert!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut state, "test")).is_err()
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "test")).is_err()
     );
 
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "test not-a-number"
     ))
     .is_err());
 
     assert!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut state, "bar")).is_err()
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "bar")).is_err()
     );
 
     assert!(
-        smol::block_on(dispatcher.dispatch(&mut nodes, &mut errors, &mut state, "bar player"))
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "bar player"))
             .is_err()
     );
 
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "bar player four"
     ))
@@ -374,7 +343,6 @@ fn command_macro() {
 
     assert!(smol::block_on(dispatcher.dispatch(
         &mut nodes,
-        &mut errors,
         &mut state,
         "bar PLAYER 28"
     ))
@@ -437,7 +405,7 @@ fn help_command() {
     // };
 
     // assert!(dispatcher
-    //     .dispatch(&mut nodes, &mut errors, &mut ctx, "help 0")
+    //     .dispatch(&mut nodes, &mut ctx, "help 0")
     //     .is_ok());
     // assert_eq!(ctx.usages, vec!["/help <page>"]);
     // assert_eq!(
@@ -446,7 +414,7 @@ fn help_command() {
     // );
 
     // assert!(dispatcher
-    //     .dispatch(&mut nodes, &mut errors, &mut ctx, "help 1")
+    //     .dispatch(&mut nodes, &mut ctx, "help 1")
     //     .is_ok());
     // assert!(ctx.usages.is_empty());
     // assert!(ctx.descriptions.is_empty());
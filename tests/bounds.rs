@@ -0,0 +1,104 @@
+use lieutenant::{command, CommandDispatcher, Context, DispatchError, RegisterError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    executed: Vec<String>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "give <count>")]
+async fn give(state: &mut State, #[arg(min = 1, max = 64)] count: u32) -> Result<(), Error> {
+    state.executed.push(format!("give {}", count));
+    Ok(())
+}
+
+#[command(usage = "depth <y>")]
+async fn depth(state: &mut State, #[arg(min = "-64")] y: i32) -> Result<(), Error> {
+    state.executed.push(format!("depth {}", y));
+    Ok(())
+}
+
+#[command(usage = "level <small>")]
+async fn level_small(state: &mut State, #[arg(max = 9)] small: u32) -> Result<(), Error> {
+    state.executed.push(format!("small {}", small));
+    Ok(())
+}
+
+#[command(usage = "level <tiny>")]
+async fn level_tiny(state: &mut State, #[arg(max = 9)] tiny: u32) -> Result<(), Error> {
+    state.executed.push(format!("tiny {}", tiny));
+    Ok(())
+}
+
+#[command(usage = "level <any>")]
+async fn level_any(state: &mut State, any: u32) -> Result<(), Error> {
+    state.executed.push(format!("any {}", any));
+    Ok(())
+}
+
+#[test]
+fn values_outside_of_bounds_are_rejected() {
+    let dispatcher = CommandDispatcher::default().with(give).with(depth);
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give 64")).unwrap();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "depth -64")).unwrap();
+    assert_eq!(state.executed, vec!["give 64", "depth -64"]);
+
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give 65")),
+        Err(DispatchError::OutOfRange {
+            name: "count".into(),
+            bounds: "between 1 and 64".into(),
+            range: 5..7,
+        })
+    );
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "depth -65")),
+        Err(DispatchError::OutOfRange {
+            name: "y".into(),
+            bounds: "at least -64".into(),
+            range: 6..9,
+        })
+    );
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "give many")),
+        Err(DispatchError::ArgumentParse {
+            name: "count".into(),
+            expected: "u32 between 1 and 64".into(),
+            range: 5..9,
+        })
+    );
+}
+
+#[test]
+fn differently_bounded_arguments_are_not_merged() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(level_small).unwrap();
+    dispatcher.register(level_any).unwrap();
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "level 5")).unwrap();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "level 50")).unwrap();
+    assert_eq!(state.executed, vec!["small 5", "any 50"]);
+}
+
+#[test]
+fn equally_bounded_arguments_are_merged() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(level_small).unwrap();
+    assert!(matches!(
+        dispatcher.register(level_tiny),
+        Err(RegisterError::OverlappingCommands)
+    ));
+}
@@ -0,0 +1,124 @@
+use lieutenant::{argument, literal, CommandDispatcher, Context, DispatchError, RegisterError};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    op: bool,
+    gamemode: Option<String>,
+    executed: Vec<&'static str>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+fn is_op(state: &State) -> bool {
+    state.op
+}
+
+fn dispatch(
+    dispatcher: &CommandDispatcher<State>,
+    state: &mut State,
+    command: &str,
+) -> Result<(), DispatchError<Error>> {
+    smol::block_on(dispatcher.dispatch(&mut Vec::new(), state, command))
+}
+
+#[test]
+fn builder_registers_command_tree() {
+    let tree = literal("gamemode")
+        .executes(|state: &mut State, _| {
+            Box::pin(async move {
+                state.executed.push("gamemode");
+                Ok(())
+            })
+        })
+        .then(
+            argument::<String>("mode")
+                .requires(is_op)
+                .executes(|state, mut context| {
+                    Box::pin(async move {
+                        state.gamemode = context.take::<String>("mode");
+                        Ok(())
+                    })
+                }),
+        )
+        .then(literal("query").executes(|state: &mut State, _| {
+            Box::pin(async move {
+                state.executed.push("query");
+                Ok(())
+            })
+        }));
+
+    let mut dispatcher = CommandDispatcher::default();
+    assert_eq!(dispatcher.register_tree(tree).unwrap().len(), 3);
+    assert_eq!(
+        dispatcher
+            .commands()
+            .map(|spec| spec.usage())
+            .collect::<Vec<_>>(),
+        vec!["gamemode", "gamemode <mode>", "gamemode query"]
+    );
+
+    let mut state = State::default();
+    dispatch(&dispatcher, &mut state, "gamemode").unwrap();
+    dispatch(&dispatcher, &mut state, "gamemode query").unwrap();
+    assert_eq!(state.executed, vec!["gamemode", "query"]);
+
+    assert_eq!(
+        dispatch(&dispatcher, &mut state, "gamemode creative"),
+        Err(DispatchError::UnknownArgument { range: 9..17 })
+    );
+    state.op = true;
+    dispatch(&dispatcher, &mut state, "gamemode creative").unwrap();
+    assert_eq!(state.gamemode.as_deref(), Some("creative"));
+}
+
+#[test]
+fn failed_tree_registration_is_rolled_back() {
+    let mut dispatcher = CommandDispatcher::<State>::default();
+    dispatcher
+        .register_tree(
+            literal("kill")
+                .then(argument::<u32>("count").executes(|_, _| Box::pin(async { Ok(()) }))),
+        )
+        .unwrap();
+
+    let tree = literal("kill")
+        .then(literal("all").executes(|_, _| Box::pin(async { Ok(()) })))
+        .then(argument::<u32>("count").executes(|_, _| Box::pin(async { Ok(()) })));
+    assert!(matches!(
+        dispatcher.register_tree(tree),
+        Err(RegisterError::OverlappingCommands)
+    ));
+    assert_eq!(dispatcher.commands().count(), 1);
+}
+
+#[test]
+fn executors_capture_state() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let captured = Arc::clone(&log);
+    let tree = literal("log").then(argument::<String>("message").executes(
+        move |_: &mut State, context| {
+            let log = Arc::clone(&captured);
+            Box::pin(async move {
+                log.lock().unwrap().push(context.input().to_owned());
+                Ok(())
+            })
+        },
+    ));
+
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register_tree(tree).unwrap();
+
+    let mut state = State::default();
+    dispatch(&dispatcher, &mut state, "log hello").unwrap();
+    dispatch(&dispatcher, &mut state, "log world").unwrap();
+    assert_eq!(*log.lock().unwrap(), vec!["log hello", "log world"]);
+}
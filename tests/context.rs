@@ -0,0 +1,92 @@
+use lieutenant::{argument, literal, CommandDispatcher, CommandSpec, Context, MatchedNode};
+use std::ops::Range;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    input: String,
+    path: Vec<MatchedNode>,
+    ranges: Vec<Option<Range<usize>>>,
+    values: Vec<Option<i32>>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+fn teleport() -> Vec<CommandSpec<State>> {
+    literal("teleport")
+        .then(argument::<i32>("x").then(argument::<i32>("y").executes(
+            |state: &mut State, context| {
+                Box::pin(async move {
+                    state.input = context.input().to_owned();
+                    state.path = context.path().to_vec();
+                    state.ranges = vec![context.range("x"), context.range("y")];
+                    state.values = vec![
+                        context.get::<i32>("x").copied(),
+                        context.get::<i32>("y").copied(),
+                    ];
+                    Ok(())
+                })
+            },
+        )))
+        .build()
+}
+
+fn node(name: &'static str, literal: bool, range: Range<usize>) -> MatchedNode {
+    MatchedNode {
+        name: name.into(),
+        literal,
+        range,
+    }
+}
+
+#[test]
+fn context_exposes_input_path_and_arguments() {
+    let mut dispatcher = CommandDispatcher::default();
+    for spec in teleport() {
+        dispatcher.register(spec).unwrap();
+    }
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "teleport 10 -5")).unwrap();
+
+    assert_eq!(state.input, "teleport 10 -5");
+    assert_eq!(
+        state.path,
+        vec![
+            node("teleport", true, 0..8),
+            node("x", false, 9..11),
+            node("y", false, 12..14),
+        ]
+    );
+    assert_eq!(state.ranges, vec![Some(9..11), Some(12..14)]);
+    assert_eq!(state.values, vec![Some(10), Some(-5)]);
+}
+
+#[test]
+fn aliased_literals_are_named_after_the_command() {
+    let mut dispatcher = CommandDispatcher::default();
+    for mut spec in teleport() {
+        spec.aliases.push("tp".into());
+        dispatcher.register(spec).unwrap();
+    }
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "tp 1 2")).unwrap();
+
+    assert_eq!(
+        state.path,
+        vec![
+            node("teleport", true, 0..2),
+            node("x", false, 3..4),
+            node("y", false, 5..6),
+        ]
+    );
+}
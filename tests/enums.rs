@@ -0,0 +1,94 @@
+use lieutenant::{command, ArgumentKind, CommandDispatcher, Context, DispatchError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Clone, Copy, Debug, PartialEq, ArgumentKind)]
+enum GameMode {
+    #[arg(aliases("s", "0"))]
+    Survival,
+    #[arg(aliases = "c")]
+    Creative,
+    Adventure,
+    #[arg(rename = "spectate")]
+    SpectatorMode,
+}
+
+#[derive(Default)]
+struct State {
+    gamemode: Option<GameMode>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "gamemode <mode>")]
+async fn gamemode(state: &mut State, mode: GameMode) -> Result<(), Error> {
+    state.gamemode = Some(mode);
+    Ok(())
+}
+
+#[command(usage = "allow <modes>...")]
+async fn allow(state: &mut State, modes: Vec<GameMode>) -> Result<(), Error> {
+    state.gamemode = modes.last().copied();
+    Ok(())
+}
+
+fn dispatch(state: &mut State, command: &str) -> Result<(), DispatchError<Error>> {
+    let dispatcher = CommandDispatcher::default().with(gamemode);
+    let mut nodes = Vec::new();
+    smol::block_on(dispatcher.dispatch(&mut nodes, state, command))
+}
+
+#[test]
+fn variants_are_parsed_by_name() {
+    let mut state = State::default();
+    for (input, mode) in [
+        ("gamemode survival", GameMode::Survival),
+        ("gamemode s", GameMode::Survival),
+        ("gamemode 0", GameMode::Survival),
+        ("gamemode c", GameMode::Creative),
+        ("gamemode adventure", GameMode::Adventure),
+        ("gamemode spectate", GameMode::SpectatorMode),
+    ]
+    .iter()
+    {
+        dispatch(&mut state, input).unwrap();
+        assert_eq!(state.gamemode, Some(*mode));
+    }
+
+    assert_eq!(
+        dispatch(&mut state, "gamemode spectator_mode"),
+        Err(DispatchError::ArgumentParse {
+            name: "mode".into(),
+            expected: "one of survival, creative, adventure, spectate".into(),
+            range: 9..23,
+        })
+    );
+}
+
+#[test]
+fn variants_are_suggested() {
+    let dispatcher = CommandDispatcher::default().with(gamemode);
+    let suggestions = smol::block_on(dispatcher.suggest(&State::default(), "gamemode s", 10));
+    assert_eq!(suggestions.suggestions, vec!["spectate", "survival"]);
+}
+
+#[test]
+fn variants_are_suggested_for_every_element() {
+    let dispatcher = CommandDispatcher::default().with(allow);
+    let state = State::default();
+    for (input, expected) in [
+        ("allow c", vec!["creative"]),
+        ("allow creative s", vec!["spectate", "survival"]),
+        ("allow creative survival a", vec!["adventure"]),
+    ]
+    .iter()
+    {
+        let suggestions = smol::block_on(dispatcher.suggest(&state, input, input.len()));
+        assert_eq!(&suggestions.suggestions, expected);
+    }
+}
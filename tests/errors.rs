@@ -0,0 +1,157 @@
+use lieutenant::{command, CommandDispatcher, Context, DispatchError, ErrorRenderer};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {
+    #[error("{0}")]
+    Custom(String),
+}
+
+struct State;
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "teleport <x> <y>")]
+async fn teleport(_state: &mut State, _x: i32, _y: i32) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "teleport spawn")]
+async fn teleport_spawn(_state: &mut State) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "fail")]
+async fn fail(_state: &mut State) -> Result<(), Error> {
+    Err(Error::Custom("failed".into()))
+}
+
+fn dispatch(command: &str) -> Result<(), DispatchError<Error>> {
+    let dispatcher = CommandDispatcher::default()
+        .with(teleport)
+        .with(teleport_spawn)
+        .with(fail);
+
+    let mut nodes = Vec::new();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut State, command))
+}
+
+#[test]
+fn unknown_command() {
+    assert_eq!(
+        dispatch("tp 1 2"),
+        Err(DispatchError::UnknownCommand { range: 0..2 })
+    );
+    assert_eq!(
+        dispatch(""),
+        Err(DispatchError::UnknownCommand { range: 0..0 })
+    );
+}
+
+#[test]
+fn argument_parse_failure() {
+    let error = dispatch("teleport foo 2").unwrap_err();
+    assert_eq!(
+        error,
+        DispatchError::ArgumentParse {
+            name: "x".into(),
+            expected: "i32".into(),
+            range: 9..12,
+        }
+    );
+    assert_eq!(error.to_string(), "expected i32 at position 9");
+
+    assert_eq!(
+        dispatch("teleport 1 foo"),
+        Err(DispatchError::ArgumentParse {
+            name: "y".into(),
+            expected: "i32".into(),
+            range: 11..14,
+        })
+    );
+}
+
+#[test]
+fn incomplete_command() {
+    assert_eq!(
+        dispatch("teleport 1"),
+        Err(DispatchError::Incomplete { position: 10 })
+    );
+    assert_eq!(
+        dispatch("teleport"),
+        Err(DispatchError::Incomplete { position: 8 })
+    );
+}
+
+#[test]
+fn trailing_input() {
+    assert_eq!(
+        dispatch("teleport spawn now"),
+        Err(DispatchError::TrailingInput { range: 15..18 })
+    );
+    assert_eq!(
+        dispatch("teleport 1 2 3"),
+        Err(DispatchError::TrailingInput { range: 13..14 })
+    );
+}
+
+#[test]
+fn handler_failure() {
+    let error = dispatch("fail").unwrap_err();
+    assert_eq!(
+        error,
+        DispatchError::Handler(vec![Error::Custom("failed".into())])
+    );
+    assert_eq!(error.position(), None);
+    assert_eq!(error.to_string(), "failed");
+}
+
+#[test]
+fn render_caret() {
+    let renderer = ErrorRenderer::default();
+
+    let input = "teleport foo 2";
+    assert_eq!(
+        renderer.render(input, &dispatch(input).unwrap_err()),
+        "expected i32 at position 9: ...leport foo<--[HERE]"
+    );
+
+    let input = "tp";
+    assert_eq!(
+        renderer.render(input, &dispatch(input).unwrap_err()),
+        "unknown command at position 0: tp<--[HERE]"
+    );
+
+    let input = "fail";
+    assert_eq!(
+        renderer.render(input, &dispatch(input).unwrap_err()),
+        "failed"
+    );
+}
+
+#[test]
+fn render_caret_with_custom_window() {
+    let renderer = ErrorRenderer {
+        context: 20,
+        ellipsis: "…".into(),
+        marker: " <- here".into(),
+    };
+
+    let input = "teleport foo 2";
+    assert_eq!(
+        renderer.render(input, &dispatch(input).unwrap_err()),
+        "expected i32 at position 9: teleport foo <- here"
+    );
+
+    let renderer = ErrorRenderer {
+        context: 3,
+        ..ErrorRenderer::new()
+    };
+    assert_eq!(
+        renderer.render(input, &dispatch(input).unwrap_err()),
+        "expected i32 at position 9: ...foo<--[HERE]"
+    );
+}
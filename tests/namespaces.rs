@@ -0,0 +1,140 @@
+use lieutenant::{command, CommandDispatcher, Context, DispatchError, RegisterError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    executed: Vec<&'static str>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "kill <target>", namespace = "combat")]
+async fn combat_kill(state: &mut State, _target: String) -> Result<(), Error> {
+    state.executed.push("combat");
+    Ok(())
+}
+
+#[command(usage = "kill <target>", namespace = "mobs")]
+async fn mobs_kill(state: &mut State, _target: String) -> Result<(), Error> {
+    state.executed.push("mobs");
+    Ok(())
+}
+
+#[command(usage = "kill <target>")]
+async fn kill(state: &mut State, _target: String) -> Result<(), Error> {
+    state.executed.push("kill");
+    Ok(())
+}
+
+#[command(usage = "teleport <target>", aliases = "kill")]
+async fn teleport(state: &mut State, _target: String) -> Result<(), Error> {
+    state.executed.push("teleport");
+    Ok(())
+}
+
+#[command(usage = "<target>", namespace = "mobs")]
+async fn parser_first(_state: &mut State, _target: String) -> Result<(), Error> {
+    Ok(())
+}
+
+fn dispatch(
+    dispatcher: &CommandDispatcher<State>,
+    command: &str,
+) -> Result<Vec<&'static str>, DispatchError<Error>> {
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut Vec::new(), &mut state, command))?;
+    Ok(state.executed)
+}
+
+#[test]
+fn namespaced_commands_stay_reachable() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(combat_kill).unwrap();
+    let mobs = dispatcher.register(mobs_kill).unwrap();
+
+    assert_eq!(
+        dispatch(&dispatcher, "combat:kill zombie"),
+        Ok(vec!["combat"])
+    );
+    assert_eq!(dispatch(&dispatcher, "mobs:kill zombie"), Ok(vec!["mobs"]));
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["combat"]));
+
+    dispatcher.prefer_namespace("kill", "mobs");
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["mobs"]));
+
+    dispatcher.unregister(mobs).unwrap();
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["combat"]));
+    assert_eq!(
+        dispatch(&dispatcher, "mobs:kill zombie"),
+        Err(DispatchError::UnknownCommand { range: 0..9 })
+    );
+}
+
+#[test]
+fn preference_applies_to_later_registrations() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.prefer_namespace("kill", "mobs");
+    dispatcher.register(combat_kill).unwrap();
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["combat"]));
+
+    dispatcher.register(mobs_kill).unwrap();
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["mobs"]));
+}
+
+#[test]
+fn commands_without_namespace_own_the_bare_name() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(kill).unwrap();
+    dispatcher.register(combat_kill).unwrap();
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["kill"]));
+    assert_eq!(
+        dispatch(&dispatcher, "combat:kill zombie"),
+        Ok(vec!["combat"])
+    );
+
+    // Regardless of the order of registration.
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(combat_kill).unwrap();
+    let kill_handle = dispatcher.register(kill).unwrap();
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["kill"]));
+    assert_eq!(
+        dispatch(&dispatcher, "combat:kill zombie"),
+        Ok(vec!["combat"])
+    );
+
+    dispatcher.unregister(kill_handle).unwrap();
+    assert_eq!(dispatch(&dispatcher, "kill zombie"), Ok(vec!["combat"]));
+
+    assert!(matches!(
+        dispatcher.register(parser_first),
+        Err(RegisterError::NamespacedParser)
+    ));
+}
+
+#[test]
+fn preferences_leave_aliases_alone() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(teleport).unwrap();
+    dispatcher.prefer_namespace("kill", "mobs");
+    assert_eq!(dispatch(&dispatcher, "kill 2"), Ok(vec!["teleport"]));
+}
+
+#[test]
+fn unregistered_bare_names_do_not_replace_aliases() {
+    let mut dispatcher = CommandDispatcher::default();
+    let combat = dispatcher.register(combat_kill).unwrap();
+    dispatcher.unregister(combat).unwrap();
+    dispatcher.register(teleport).unwrap();
+
+    assert!(matches!(
+        dispatcher.register(mobs_kill),
+        Err(RegisterError::AliasConflict)
+    ));
+    assert_eq!(dispatch(&dispatcher, "kill 3"), Ok(vec!["teleport"]));
+}
@@ -0,0 +1,112 @@
+use lieutenant::{command, CommandDispatcher, Context, DispatchError, RegisterError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {
+    #[error("not a block")]
+    NotABlock,
+}
+
+#[derive(Default)]
+struct State {
+    executed: Vec<&'static str>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "kill <target>")]
+async fn kill(state: &mut State, _target: String) -> Result<(), Error> {
+    state.executed.push("kill");
+    Ok(())
+}
+
+#[command(usage = "kill <entity>")]
+async fn kill_entity(state: &mut State, _entity: String) -> Result<(), Error> {
+    state.executed.push("kill_entity");
+    Ok(())
+}
+
+#[command(usage = "kill <target> [reason]")]
+async fn kill_with_reason(
+    state: &mut State,
+    _target: String,
+    _reason: Option<String>,
+) -> Result<(), Error> {
+    state.executed.push("kill_with_reason");
+    Ok(())
+}
+
+#[command(usage = "kill <target> now")]
+async fn kill_now(state: &mut State, _target: String) -> Result<(), Error> {
+    state.executed.push("kill_now");
+    Ok(())
+}
+
+#[command(usage = "place <name>", overload)]
+async fn place_block(state: &mut State, name: String) -> Result<(), Error> {
+    if name != "stone" {
+        return Err(Error::NotABlock);
+    }
+    state.executed.push("place_block");
+    Ok(())
+}
+
+#[command(usage = "place <name>", overload)]
+async fn place_entity(state: &mut State, _name: String) -> Result<(), Error> {
+    state.executed.push("place_entity");
+    Ok(())
+}
+
+#[command(usage = "place <name>")]
+async fn place(state: &mut State, _name: String) -> Result<(), Error> {
+    state.executed.push("place");
+    Ok(())
+}
+
+#[test]
+fn duplicate_commands_are_rejected() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(kill).unwrap();
+    assert!(matches!(
+        dispatcher.register(kill_entity),
+        Err(RegisterError::OverlappingCommands)
+    ));
+    assert!(matches!(
+        dispatcher.register(kill_with_reason),
+        Err(RegisterError::OverlappingCommands)
+    ));
+    dispatcher.register(kill_now).unwrap();
+
+    // The rejected commands left no trace in the dispatcher.
+    assert_eq!(dispatcher.commands().count(), 2);
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "kill zombie")).unwrap();
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "kill zombie slowly")),
+        Err(DispatchError::UnknownArgument { range: 12..18 })
+    );
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "kill zombie now")).unwrap();
+    assert_eq!(state.executed, vec!["kill", "kill_now"]);
+}
+
+#[test]
+fn overloads_are_tried_in_order() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher.register(place_block).unwrap();
+    dispatcher.register(place_entity).unwrap();
+    assert!(matches!(
+        dispatcher.register(place),
+        Err(RegisterError::OverlappingCommands)
+    ));
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "place stone")).unwrap();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "place creeper")).unwrap();
+    assert_eq!(state.executed, vec!["place_block", "place_entity"]);
+}
@@ -0,0 +1,99 @@
+use lieutenant::{
+    command, ArgumentChecker, ArgumentKind, CheckerKey, CommandDispatcher, Context, ParsedValue,
+    StringReader,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    executed: Vec<String>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+static PARSES: AtomicUsize = AtomicUsize::new(0);
+
+/// Number which counts how often it has been parsed.
+#[derive(Clone, Debug, PartialEq)]
+struct Counted(u32);
+
+#[derive(Clone, Default)]
+struct CountedChecker;
+
+impl ArgumentChecker<State> for CountedChecker {
+    fn parse<'a, 'b>(
+        &self,
+        _ctx: &'a State,
+        input: &'a mut StringReader<'b>,
+    ) -> Pin<Box<dyn Future<Output = Option<ParsedValue>> + Send + 'a>> {
+        Box::pin(async move {
+            PARSES.fetch_add(1, Ordering::SeqCst);
+            let value = input.read_until(" ").parse().ok()?;
+            Some(Box::new(Counted(value)) as ParsedValue)
+        })
+    }
+
+    fn key(&self) -> CheckerKey {
+        CheckerKey::of::<Self>()
+    }
+
+    fn default() -> Self {
+        CountedChecker
+    }
+
+    fn box_clone(&self) -> Box<dyn ArgumentChecker<State>> {
+        Box::new(self.clone())
+    }
+}
+
+impl ArgumentKind<State> for Counted {
+    type Checker = CountedChecker;
+}
+
+#[command(usage = "count <n>")]
+async fn count(state: &mut State, n: Counted) -> Result<(), Error> {
+    state.executed.push(format!("count {}", n.0));
+    Ok(())
+}
+
+#[command(usage = "kill <target>")]
+async fn kill(state: &mut State, target: String) -> Result<(), Error> {
+    state.executed.push(format!("kill {}", target));
+    Ok(())
+}
+
+#[command(usage = "kill <entity> now")]
+async fn kill_now(state: &mut State, entity: String) -> Result<(), Error> {
+    state.executed.push(format!("kill {} now", entity));
+    Ok(())
+}
+
+#[test]
+fn arguments_are_parsed_once() {
+    let dispatcher = CommandDispatcher::default().with(count);
+
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut Vec::new(), &mut state, "count 7")).unwrap();
+    assert_eq!(state.executed, vec!["count 7"]);
+    assert_eq!(PARSES.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn arguments_are_named_by_each_command() {
+    let dispatcher = CommandDispatcher::default().with(kill).with(kill_now);
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "kill zombie")).unwrap();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "kill creeper now")).unwrap();
+    assert_eq!(state.executed, vec!["kill zombie", "kill creeper now"]);
+}
@@ -0,0 +1,83 @@
+use lieutenant::{command, CommandDispatcher, Context};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    executed: Vec<&'static str>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "find <name>")]
+async fn find_by_name(state: &mut State, _name: String) -> Result<(), Error> {
+    state.executed.push("name");
+    Ok(())
+}
+
+#[command(usage = "find <id>")]
+async fn find_by_id(state: &mut State, #[arg(priority = 10)] _id: u32) -> Result<(), Error> {
+    state.executed.push("id");
+    Ok(())
+}
+
+#[command(usage = "find all")]
+async fn find_all(state: &mut State) -> Result<(), Error> {
+    state.executed.push("all");
+    Ok(())
+}
+
+#[command(usage = "kill <entity>")]
+async fn kill_entity(state: &mut State, _entity: String) -> Result<(), Error> {
+    state.executed.push("entity");
+    Ok(())
+}
+
+#[command(usage = "kill <count>", priority = 5)]
+async fn kill_count(state: &mut State, _count: u32) -> Result<(), Error> {
+    state.executed.push("count");
+    Ok(())
+}
+
+fn dispatch(dispatcher: &CommandDispatcher<State>, command: &str) -> Vec<&'static str> {
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut Vec::new(), &mut state, command)).unwrap();
+    state.executed
+}
+
+#[test]
+fn literals_are_tried_before_parsers() {
+    let dispatcher = CommandDispatcher::default()
+        .with(find_by_name)
+        .with(find_all);
+
+    assert_eq!(dispatch(&dispatcher, "find all"), vec!["all"]);
+    assert_eq!(dispatch(&dispatcher, "find bob"), vec!["name"]);
+}
+
+#[test]
+fn higher_priority_parsers_are_tried_first() {
+    for dispatcher in [
+        CommandDispatcher::default()
+            .with(find_by_name)
+            .with(find_by_id),
+        CommandDispatcher::default()
+            .with(find_by_id)
+            .with(find_by_name),
+    ] {
+        assert_eq!(dispatch(&dispatcher, "find 42"), vec!["id"]);
+        assert_eq!(dispatch(&dispatcher, "find bob"), vec!["name"]);
+    }
+
+    // Without priorities, siblings are tried in registration order.
+    let dispatcher = CommandDispatcher::default()
+        .with(find_by_name)
+        .with(kill_entity)
+        .with(kill_count);
+    assert_eq!(dispatch(&dispatcher, "kill 3"), vec!["count"]);
+}
@@ -0,0 +1,88 @@
+use lieutenant::{command, provider, CommandDispatcher, Context, DispatchError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {
+    #[error("no world loaded")]
+    NoWorld,
+}
+
+#[derive(Default)]
+struct State {
+    world: Option<&'static str>,
+    executed: Vec<String>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+struct WorldHandle(&'static str);
+
+#[provider]
+async fn provide_world(state: &State) -> Result<WorldHandle, Error> {
+    state.world.map(WorldHandle).ok_or(Error::NoWorld)
+}
+
+#[command(usage = "setblock <x>")]
+async fn setblock(state: &mut State, x: i32, world: WorldHandle) -> Result<(), Error> {
+    state.executed.push(format!("{} {}", world.0, x));
+    Ok(())
+}
+
+#[command(usage = "ping")]
+async fn ping(_: &mut State) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "world")]
+async fn world_name(_: &mut State, world: WorldHandle) -> Result<(), Error> {
+    assert_eq!(world.0, "overworld");
+    Ok(())
+}
+
+#[test]
+fn provided_parameters_are_resolved() {
+    let dispatcher = CommandDispatcher::default().with(setblock);
+
+    let mut nodes = Vec::new();
+    let mut state = State {
+        world: Some("overworld"),
+        ..State::default()
+    };
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "setblock 4")).unwrap();
+    assert_eq!(state.executed, vec!["overworld 4"]);
+}
+
+#[test]
+fn provider_errors_end_the_command() {
+    let dispatcher = CommandDispatcher::default().with(setblock);
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "setblock 4")),
+        Err(DispatchError::Handler(vec![Error::NoWorld]))
+    );
+    assert!(state.executed.is_empty());
+}
+
+#[test]
+fn unnamed_contexts_are_passed_to_providers() {
+    let dispatcher = CommandDispatcher::default().with(ping).with(world_name);
+
+    let mut nodes = Vec::new();
+    let mut state = State {
+        world: Some("overworld"),
+        ..State::default()
+    };
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "ping")).unwrap();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "world")).unwrap();
+
+    let mut state = State::default();
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "world")),
+        Err(DispatchError::Handler(vec![Error::NoWorld]))
+    );
+}
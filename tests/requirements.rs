@@ -0,0 +1,128 @@
+use lieutenant::{command, CommandDispatcher, Context, DispatchError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    permissions: Vec<&'static str>,
+    in_game: bool,
+    executed: Vec<&'static str>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+
+    fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.contains(&permission)
+    }
+}
+
+fn in_game(state: &State) -> bool {
+    state.in_game
+}
+
+#[command(usage = "stop", permission = "server.op")]
+async fn stop(state: &mut State) -> Result<(), Error> {
+    state.executed.push("stop");
+    Ok(())
+}
+
+#[command(usage = "gamemode <mode>")]
+async fn gamemode(state: &mut State, _mode: String) -> Result<(), Error> {
+    state.executed.push("gamemode");
+    Ok(())
+}
+
+#[command(
+    usage = "gamemode <mode> <player>",
+    permission = "server.op",
+    requires = "in_game"
+)]
+async fn gamemode_other(state: &mut State, _mode: String, _player: String) -> Result<(), Error> {
+    state.executed.push("gamemode_other");
+    Ok(())
+}
+
+fn dispatcher() -> CommandDispatcher<State> {
+    CommandDispatcher::default()
+        .with(stop)
+        .with(gamemode)
+        .with(gamemode_other)
+}
+
+#[test]
+fn requirements_hide_commands_from_dispatch() {
+    let dispatcher = dispatcher();
+    let mut nodes = Vec::new();
+
+    let mut state = State::default();
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "stop")),
+        Err(DispatchError::UnknownCommand { range: 0..4 })
+    );
+    assert!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "gamemode creative")).is_ok()
+    );
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "gamemode creative bob")),
+        Err(DispatchError::TrailingInput { range: 18..21 })
+    );
+
+    let mut state = State {
+        permissions: vec!["server.op"],
+        ..State::default()
+    };
+    assert!(smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "stop")).is_ok());
+    assert!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "gamemode creative bob"))
+            .is_err()
+    );
+
+    state.in_game = true;
+    assert!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "gamemode creative bob"))
+            .is_ok()
+    );
+    assert_eq!(state.executed, vec!["stop", "gamemode_other"]);
+}
+
+#[test]
+fn requirements_hide_commands_from_suggestions() {
+    let dispatcher = dispatcher();
+
+    let state = State::default();
+    let suggestions = smol::block_on(dispatcher.suggest(&state, "", 0));
+    assert_eq!(suggestions.suggestions, vec!["gamemode"]);
+
+    let state = State {
+        permissions: vec!["server.op"],
+        ..State::default()
+    };
+    let suggestions = smol::block_on(dispatcher.suggest(&state, "", 0));
+    assert_eq!(suggestions.suggestions, vec!["gamemode", "stop"]);
+}
+
+#[test]
+fn permissions_are_denied_by_default() {
+    struct Guest;
+
+    impl Context for Guest {
+        type Error = Error;
+        type Ok = ();
+    }
+
+    #[command(usage = "stop", permission = "server.op")]
+    async fn stop(_guest: &mut Guest) -> Result<(), Error> {
+        Ok(())
+    }
+
+    let dispatcher = CommandDispatcher::default().with(stop);
+    let mut nodes = Vec::new();
+    assert_eq!(
+        smol::block_on(dispatcher.dispatch(&mut nodes, &mut Guest, "stop")),
+        Err(DispatchError::UnknownCommand { range: 0..4 })
+    );
+}
@@ -0,0 +1,131 @@
+use lieutenant::{command, CommandDispatcher, Context};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+struct State {
+    players: Vec<String>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "teleport <x> <y>")]
+async fn teleport(_state: &mut State, _x: i32, _y: i32) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "teleport spawn")]
+async fn teleport_spawn(_state: &mut State) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "tell <message>")]
+async fn tell(_state: &mut State, _message: String) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn online_players(state: &State, partial: &str) -> Vec<String> {
+    state
+        .players
+        .iter()
+        .filter(|player| player.starts_with(partial))
+        .cloned()
+        .collect()
+}
+
+#[command(usage = "msg <player> <message>")]
+async fn msg(
+    _state: &mut State,
+    #[arg(suggestions = "online_players")] _player: String,
+    _message: String,
+) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "gamemode creative")]
+async fn gamemode_creative(_state: &mut State) -> Result<(), Error> {
+    Ok(())
+}
+
+#[command(usage = "gamemode survival")]
+async fn gamemode_survival(_state: &mut State) -> Result<(), Error> {
+    Ok(())
+}
+
+fn state() -> State {
+    State {
+        players: vec!["alice".into(), "bob".into(), "bert".into()],
+    }
+}
+
+fn dispatcher() -> CommandDispatcher<State> {
+    CommandDispatcher::default()
+        .with(teleport)
+        .with(teleport_spawn)
+        .with(tell)
+        .with(msg)
+        .with(gamemode_creative)
+        .with(gamemode_survival)
+}
+
+#[test]
+fn suggest_root_literals() {
+    let dispatcher = dispatcher();
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "", 0));
+    assert_eq!(suggestions.range, 0..0);
+    assert_eq!(
+        suggestions.suggestions,
+        vec!["gamemode", "msg", "teleport", "tell"]
+    );
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "te", 2));
+    assert_eq!(suggestions.range, 0..2);
+    assert_eq!(suggestions.suggestions, vec!["teleport", "tell"]);
+}
+
+#[test]
+fn suggest_child_literals() {
+    let dispatcher = dispatcher();
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "gamemode s", 10));
+    assert_eq!(suggestions.range, 9..10);
+    assert_eq!(suggestions.suggestions, vec!["survival"]);
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "teleport ", 9));
+    assert_eq!(suggestions.range, 9..9);
+    assert_eq!(suggestions.suggestions, vec!["spawn"]);
+}
+
+#[test]
+fn suggest_ignores_input_after_cursor() {
+    let dispatcher = dispatcher();
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "gamemode creative", 10));
+    assert_eq!(suggestions.range, 9..10);
+    assert_eq!(suggestions.suggestions, vec!["creative"]);
+}
+
+#[test]
+fn suggest_nothing_for_unknown_command() {
+    let dispatcher = dispatcher();
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "unknown ", 8));
+    assert!(suggestions.is_empty());
+}
+
+#[test]
+fn suggest_from_provider() {
+    let dispatcher = dispatcher();
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "msg b", 5));
+    assert_eq!(suggestions.range, 4..5);
+    assert_eq!(suggestions.suggestions, vec!["bert", "bob"]);
+
+    let suggestions = smol::block_on(dispatcher.suggest(&state(), "msg bob ", 8));
+    assert!(suggestions.is_empty());
+}
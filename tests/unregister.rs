@@ -0,0 +1,99 @@
+use lieutenant::{command, CommandDispatcher, Context, DispatchError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    executed: Vec<&'static str>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "teleport <x> <y>", aliases = "tp")]
+async fn teleport(state: &mut State, _x: i32, _y: i32) -> Result<(), Error> {
+    state.executed.push("teleport");
+    Ok(())
+}
+
+#[command(usage = "teleport spawn")]
+async fn teleport_spawn(state: &mut State) -> Result<(), Error> {
+    state.executed.push("teleport_spawn");
+    Ok(())
+}
+
+#[command(usage = "teleport <x>")]
+async fn teleport_x(state: &mut State, _x: i32) -> Result<(), Error> {
+    state.executed.push("teleport_x");
+    Ok(())
+}
+
+fn dispatch(
+    dispatcher: &CommandDispatcher<State>,
+    command: &str,
+) -> Result<Vec<&'static str>, DispatchError<Error>> {
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut Vec::new(), &mut state, command))?;
+    Ok(state.executed)
+}
+
+#[test]
+fn unregister_removes_command() {
+    let mut dispatcher = CommandDispatcher::default();
+    let teleport_handle = dispatcher.register(teleport).unwrap();
+    dispatcher.register(teleport_spawn).unwrap();
+    let teleport_x_handle = dispatcher.register(teleport_x).unwrap();
+
+    let spec = dispatcher.unregister(teleport_handle).unwrap();
+    assert_eq!(spec.usage(), "teleport <x> <y>");
+    assert!(dispatcher.unregister(teleport_handle).is_none());
+    assert_eq!(dispatcher.commands().count(), 2);
+
+    assert_eq!(
+        dispatch(&dispatcher, "teleport 1 2"),
+        Err(DispatchError::TrailingInput { range: 11..12 })
+    );
+    assert_eq!(
+        dispatch(&dispatcher, "tp 1 2"),
+        Err(DispatchError::UnknownCommand { range: 0..2 })
+    );
+    assert_eq!(dispatch(&dispatcher, "teleport 1"), Ok(vec!["teleport_x"]));
+    assert_eq!(
+        dispatch(&dispatcher, "teleport spawn"),
+        Ok(vec!["teleport_spawn"])
+    );
+
+    dispatcher.unregister(teleport_x_handle).unwrap();
+    assert_eq!(
+        dispatch(&dispatcher, "teleport 1"),
+        Err(DispatchError::UnknownArgument { range: 9..10 })
+    );
+}
+
+#[test]
+fn unregistered_commands_can_be_registered_again() {
+    let mut dispatcher = CommandDispatcher::default();
+    let handle = dispatcher.register(teleport).unwrap();
+    let spec = dispatcher.unregister(handle).unwrap();
+    dispatcher.register(spec).unwrap();
+
+    assert_eq!(dispatch(&dispatcher, "tp 1 2"), Ok(vec!["teleport"]));
+}
+
+#[test]
+fn stale_handles_are_rejected() {
+    let mut dispatcher = CommandDispatcher::default();
+    let teleport_handle = dispatcher.register(teleport).unwrap();
+    dispatcher.unregister(teleport_handle).unwrap();
+
+    // The new command reuses the slot of the unregistered one.
+    let teleport_x_handle = dispatcher.register(teleport_x).unwrap();
+    assert_ne!(teleport_x_handle, teleport_handle);
+    assert!(dispatcher.unregister(teleport_handle).is_none());
+
+    assert_eq!(dispatch(&dispatcher, "teleport 1"), Ok(vec!["teleport_x"]));
+}
@@ -0,0 +1,128 @@
+use lieutenant::{argument, command, literal, CommandDispatcher, Context, DispatchError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+enum Error {}
+
+#[derive(Default)]
+struct State {
+    executed: Vec<String>,
+}
+
+impl Context for State {
+    type Error = Error;
+    type Ok = ();
+}
+
+#[command(usage = "kick <players>...")]
+async fn kick(state: &mut State, players: Vec<String>) -> Result<(), Error> {
+    state.executed.push(format!("kick {:?}", players));
+    Ok(())
+}
+
+#[command(usage = "sum <numbers>...")]
+async fn sum(state: &mut State, numbers: Vec<i32>) -> Result<(), Error> {
+    state
+        .executed
+        .push(format!("sum {}", numbers.iter().sum::<i32>()));
+    Ok(())
+}
+
+#[command(usage = "teleport <players>... to <target>")]
+async fn teleport(state: &mut State, players: Vec<String>, target: String) -> Result<(), Error> {
+    state
+        .executed
+        .push(format!("teleport {:?} {}", players, target));
+    Ok(())
+}
+
+fn dispatch(state: &mut State, command: &str) -> Result<(), DispatchError<Error>> {
+    let dispatcher = CommandDispatcher::default()
+        .with(kick)
+        .with(sum)
+        .with(teleport);
+    let mut nodes = Vec::new();
+    smol::block_on(dispatcher.dispatch(&mut nodes, state, command))
+}
+
+#[test]
+fn variadic_arguments_consume_the_remaining_tokens() {
+    let mut state = State::default();
+    dispatch(&mut state, "kick alice").unwrap();
+    dispatch(&mut state, "kick alice bob carol").unwrap();
+    dispatch(&mut state, "sum 1 2 3").unwrap();
+    assert_eq!(
+        state.executed,
+        vec![
+            r#"kick ["alice"]"#,
+            r#"kick ["alice", "bob", "carol"]"#,
+            "sum 6",
+        ]
+    );
+}
+
+#[test]
+fn variadic_arguments_stop_at_the_following_literal() {
+    let mut state = State::default();
+    dispatch(&mut state, "teleport alice bob to spawn").unwrap();
+    assert_eq!(state.executed, vec![r#"teleport ["alice", "bob"] spawn"#]);
+}
+
+#[test]
+fn variadic_builder_arguments_stop_at_the_following_literal() {
+    let mut dispatcher = CommandDispatcher::default();
+    dispatcher
+        .register_tree(
+            literal("kick").then(
+                argument::<Vec<String>>("players").then(literal("reason").then(
+                    argument::<String>("reason").executes(|state: &mut State, context| {
+                        Box::pin(async move {
+                            let players = context.get::<Vec<String>>("players");
+                            let reason = context.get::<String>("reason");
+                            state
+                                .executed
+                                .push(format!("kick {:?} {:?}", players, reason));
+                            Ok(())
+                        })
+                    }),
+                )),
+            ),
+        )
+        .unwrap();
+
+    let mut nodes = Vec::new();
+    let mut state = State::default();
+    smol::block_on(dispatcher.dispatch(&mut nodes, &mut state, "kick a b reason spam")).unwrap();
+    assert_eq!(
+        state.executed,
+        vec![r#"kick Some(["a", "b"]) Some("spam")"#]
+    );
+}
+
+#[test]
+fn variadic_arguments_require_a_value() {
+    let mut state = State::default();
+    assert_eq!(
+        dispatch(&mut state, "teleport to spawn"),
+        Err(DispatchError::ArgumentParse {
+            name: "players".into(),
+            expected: "one or more String".into(),
+            range: 9..11,
+        })
+    );
+    assert_eq!(
+        dispatch(&mut state, "sum 1 x"),
+        Err(DispatchError::TrailingInput { range: 6..7 })
+    );
+    assert!(state.executed.is_empty());
+}
+
+#[test]
+fn variadic_arguments_are_marked_in_the_usage() {
+    let dispatcher = CommandDispatcher::default().with(kick).with(teleport);
+    let usages: Vec<_> = dispatcher.commands().map(|spec| spec.usage()).collect();
+    assert_eq!(
+        usages,
+        vec!["kick <players>...", "teleport <players>... to <target>"]
+    );
+}